@@ -0,0 +1,47 @@
+//! Commit-reveal deck randomness.
+//!
+//! The backend picks a secret server seed and publishes `SHA-256(server_seed || difficulty id)`
+//! before the game starts. The player then contributes a client seed, the backend reveals the
+//! server seed, and the deck seed is derived from both. Neither side can pick a favourable deck on
+//! its own, and the difficulty is fixed before anyone sees the deck.
+
+use sha2::{Digest, Sha256};
+
+use crate::difficulty::Difficulty;
+
+/// Secret seed chosen by the backend.
+pub type ServerSeed = [u8; 32];
+
+/// `SHA-256` of the server seed and the difficulty id, published when the session starts.
+pub type Commitment = [u8; 32];
+
+/// Commits to a server seed for a game at `difficulty`.
+pub fn commit(server_seed: &ServerSeed, difficulty: Difficulty) -> Commitment {
+    Sha256::new()
+        .chain_update(server_seed)
+        .chain_update([difficulty.id()])
+        .finalize()
+        .into()
+}
+
+/// Checks a revealed server seed and the game's difficulty against the commitment published for
+/// the session.
+pub fn verify_reveal(
+    commitment: &Commitment,
+    server_seed: &ServerSeed,
+    difficulty: Difficulty,
+) -> bool {
+    commit(server_seed, difficulty) == *commitment
+}
+
+/// Derives the deck shuffle seed from both contributions.
+pub fn deck_seed(server_seed: &ServerSeed, client_seed: u64) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(server_seed)
+        .chain_update(client_seed.to_le_bytes())
+        .finalize();
+
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(seed)
+}
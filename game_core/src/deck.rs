@@ -0,0 +1,65 @@
+//! Seeded, reproducible deck shuffling.
+//!
+//! The same seed always produces the same deck on every platform, so the prover can rebuild the
+//! deck a player saw in the browser from the seed alone.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// SplitMix64 pseudo-random number generator.
+///
+/// Small, fast and fully deterministic. It is not cryptographically secure, which is fine for
+/// dealing cards from a seed that is chosen elsewhere.
+#[derive(Debug, Clone)]
+pub struct DeckRng {
+    state: u64,
+}
+
+impl DeckRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..bound` using a widening multiply (no `%` bias worth measuring).
+    pub fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+/// Builds a deck of `pairs` pairs (values `0..pairs`, each one twice) and shuffles it with
+/// Fisher–Yates driven by `seed`.
+pub fn shuffled_deck(seed: u64, pairs: u32) -> Vec<u32> {
+    let mut cards: Vec<u32> = (0..pairs).flat_map(|value| [value, value]).collect();
+    let mut rng = DeckRng::new(seed);
+
+    for i in (1..cards.len()).rev() {
+        let j = rng.below(i as u64 + 1) as usize;
+        cards.swap(i, j);
+    }
+
+    cards
+}
+
+/// Formats a seed as 16 lowercase hex digits.
+///
+/// Seeds travel through JavaScript and JSON as strings because a `u64` doesn't fit in a JS number.
+pub fn format_seed(seed: u64) -> String {
+    let mut text = String::with_capacity(16);
+    write!(text, "{:016x}", seed).expect("writing to a String can't fail");
+    text
+}
+
+/// Parses a seed written by [`format_seed`] (an optional `0x` prefix is accepted).
+pub fn parse_seed(text: &str) -> Option<u64> {
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    u64::from_str_radix(digits, 16).ok()
+}
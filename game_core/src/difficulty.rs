@@ -0,0 +1,85 @@
+//! Difficulty presets: board size, time limit and grid layout.
+//!
+//! Scores are only comparable within one difficulty, so the difficulty is part of the proven
+//! public values.
+
+/// A difficulty tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// Number of card pairs on the board.
+    pub fn pairs(self) -> u32 {
+        match self {
+            Difficulty::Easy => 6,
+            Difficulty::Normal => 8,
+            Difficulty::Hard => 12,
+            Difficulty::Expert => 18,
+        }
+    }
+
+    /// Seconds a player has to match every pair.
+    pub fn time_limit(self) -> u32 {
+        match self {
+            Difficulty::Easy => 90,
+            Difficulty::Normal => 120,
+            Difficulty::Hard => 180,
+            Difficulty::Expert => 240,
+        }
+    }
+
+    /// Board grid as `(columns, rows)`; `columns * rows` is always the number of cards.
+    pub fn grid(self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (6, 2),
+            Difficulty::Normal => (8, 2),
+            Difficulty::Hard => (8, 3),
+            Difficulty::Expert => (9, 4),
+        }
+    }
+
+    /// Stable numeric id, committed in the proof's public values.
+    pub fn id(self) -> u8 {
+        match self {
+            Difficulty::Easy => 0,
+            Difficulty::Normal => 1,
+            Difficulty::Hard => 2,
+            Difficulty::Expert => 3,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.id() == id)
+    }
+
+    /// Lowercase name used by JavaScript and JSON inputs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(name))
+    }
+}
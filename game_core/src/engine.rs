@@ -0,0 +1,313 @@
+//! The memory game rules as a plain state machine.
+//!
+//! A [`Game`] only changes through [`Command`]s and reports what happened as [`Event`]s. It knows
+//! nothing about the DOM, timers or the zkVM: the web game drives it from clicks and
+//! `setInterval`, and the SP1 program drives it from a recorded transcript.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::difficulty::Difficulty;
+use crate::scoring;
+
+/// Where a game is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Cards are dealt but the game hasn't started.
+    Idle,
+    /// Waiting for the player to flip a card.
+    Playing,
+    /// Two cards are face up and wait for [`Command::Resolve`].
+    Checking,
+    /// Every pair was matched in time.
+    Won,
+    /// The time limit was reached.
+    Lost,
+}
+
+/// Input to the state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Start the game on the dealt deck. A deck is played once: after a win or loss the game
+    /// needs a new deal.
+    Start,
+    /// Flip the card at this index.
+    Flip(usize),
+    /// Compare the two face-up cards (the web game sends this after a short reveal delay).
+    Resolve,
+    /// One second has passed.
+    Tick,
+}
+
+/// What a command changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Started,
+    CardFlipped {
+        index: usize,
+        value: u32,
+    },
+    MatchFound {
+        first: usize,
+        second: usize,
+        value: u32,
+    },
+    Mismatch {
+        first: usize,
+        second: usize,
+    },
+    Tick {
+        elapsed: u32,
+        remaining: u32,
+    },
+    Won {
+        score: u32,
+    },
+    Lost,
+}
+
+/// Why a command was refused. The game state is unchanged when this is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// The command isn't valid in the current phase.
+    WrongPhase(Phase),
+    /// The card index is outside the deck.
+    OutOfRange(usize),
+    /// The card is already part of a matched pair.
+    AlreadyMatched(usize),
+    /// The card is already face up.
+    AlreadyFlipped(usize),
+}
+
+/// A single game on a dealt deck.
+#[derive(Debug, Clone)]
+pub struct Game {
+    difficulty: Difficulty,
+    cards: Vec<u32>,
+    phase: Phase,
+    face_up: Vec<usize>,
+    matched: Vec<bool>,
+    matched_pairs: u32,
+    moves: u32,
+    elapsed: u32,
+    flips: Vec<u32>,
+}
+
+impl Game {
+    /// Deals `cards` (each value twice) without starting the game.
+    pub fn new(difficulty: Difficulty, cards: Vec<u32>) -> Self {
+        let matched = vec![false; cards.len()];
+        Self {
+            difficulty,
+            cards,
+            phase: Phase::Idle,
+            face_up: Vec::new(),
+            matched,
+            matched_pairs: 0,
+            moves: 0,
+            elapsed: 0,
+            flips: Vec::new(),
+        }
+    }
+
+    /// Starts a game and replays `flips`, resolving every pair as soon as it is face up.
+    ///
+    /// This is how the SP1 program re-runs a recorded game.
+    pub fn replay(
+        difficulty: Difficulty,
+        cards: Vec<u32>,
+        flips: &[u32],
+    ) -> Result<Self, Rejection> {
+        let mut game = Self::new(difficulty, cards);
+        game.apply(Command::Start)?;
+
+        for &flip in flips {
+            game.apply(Command::Flip(flip as usize))?;
+            if game.phase == Phase::Checking {
+                game.apply(Command::Resolve)?;
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Applies a command and returns the events it produced.
+    pub fn apply(&mut self, command: Command) -> Result<Vec<Event>, Rejection> {
+        match command {
+            Command::Start => self.start(),
+            Command::Flip(index) => self.flip(index),
+            Command::Resolve => self.resolve(),
+            Command::Tick => self.tick(),
+        }
+    }
+
+    fn start(&mut self) -> Result<Vec<Event>, Rejection> {
+        // A deck whose cards were already seen can't be played again
+        if self.phase != Phase::Idle {
+            return Err(Rejection::WrongPhase(self.phase));
+        }
+
+        self.phase = Phase::Playing;
+
+        Ok(vec![Event::Started])
+    }
+
+    fn flip(&mut self, index: usize) -> Result<Vec<Event>, Rejection> {
+        if self.phase != Phase::Playing {
+            return Err(Rejection::WrongPhase(self.phase));
+        }
+        if index >= self.cards.len() {
+            return Err(Rejection::OutOfRange(index));
+        }
+        if self.matched[index] {
+            return Err(Rejection::AlreadyMatched(index));
+        }
+        if self.face_up.contains(&index) {
+            return Err(Rejection::AlreadyFlipped(index));
+        }
+
+        self.face_up.push(index);
+        self.flips.push(index as u32);
+
+        // Two cards face up: that's one move
+        if self.face_up.len() == 2 {
+            self.moves += 1;
+            self.phase = Phase::Checking;
+        }
+
+        Ok(vec![Event::CardFlipped {
+            index,
+            value: self.cards[index],
+        }])
+    }
+
+    fn resolve(&mut self) -> Result<Vec<Event>, Rejection> {
+        if self.phase != Phase::Checking {
+            return Err(Rejection::WrongPhase(self.phase));
+        }
+
+        let (first, second) = (self.face_up[0], self.face_up[1]);
+        self.face_up.clear();
+
+        if self.cards[first] != self.cards[second] {
+            self.phase = Phase::Playing;
+            return Ok(vec![Event::Mismatch { first, second }]);
+        }
+
+        self.matched[first] = true;
+        self.matched[second] = true;
+        self.matched_pairs += 1;
+
+        let mut events = vec![Event::MatchFound {
+            first,
+            second,
+            value: self.cards[first],
+        }];
+
+        if self.is_complete() {
+            self.phase = Phase::Won;
+            events.push(Event::Won {
+                score: self.score(),
+            });
+        } else {
+            self.phase = Phase::Playing;
+        }
+
+        Ok(events)
+    }
+
+    fn tick(&mut self) -> Result<Vec<Event>, Rejection> {
+        if !self.is_active() {
+            return Err(Rejection::WrongPhase(self.phase));
+        }
+
+        self.elapsed += 1;
+
+        if self.elapsed >= self.difficulty.time_limit() {
+            self.face_up.clear();
+            self.phase = Phase::Lost;
+            return Ok(vec![Event::Lost]);
+        }
+
+        Ok(vec![Event::Tick {
+            elapsed: self.elapsed,
+            remaining: self.remaining_time(),
+        }])
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Whether the game is running (cards can be flipped or are being checked).
+    pub fn is_active(&self) -> bool {
+        matches!(self.phase, Phase::Playing | Phase::Checking)
+    }
+
+    /// Whether the game ended, won or lost.
+    pub fn is_over(&self) -> bool {
+        matches!(self.phase, Phase::Won | Phase::Lost)
+    }
+
+    pub fn cards(&self) -> &[u32] {
+        &self.cards
+    }
+
+    pub fn pair_count(&self) -> u32 {
+        self.cards.len() as u32 / 2
+    }
+
+    /// Every accepted flip in order: the transcript the SP1 program replays.
+    pub fn flips(&self) -> &[u32] {
+        &self.flips
+    }
+
+    pub fn is_face_up(&self, index: usize) -> bool {
+        self.face_up.contains(&index)
+    }
+
+    pub fn is_matched(&self, index: usize) -> bool {
+        self.matched.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn matched_pairs(&self) -> u32 {
+        self.matched_pairs
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.matched_pairs == self.pair_count()
+    }
+
+    pub fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// Seconds elapsed since the game started.
+    pub fn elapsed(&self) -> u32 {
+        self.elapsed
+    }
+
+    pub fn remaining_time(&self) -> u32 {
+        scoring::remaining_time(self.difficulty, self.elapsed)
+    }
+
+    /// Final score (see [`scoring::final_score`]). Lost or unfinished games score 0.
+    pub fn score(&self) -> u32 {
+        scoring::final_score(
+            self.difficulty,
+            self.moves,
+            self.elapsed,
+            self.phase == Phase::Won,
+        )
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(Difficulty::default(), Vec::new())
+    }
+}
@@ -0,0 +1,13 @@
+//! Platform-independent memory game logic shared by the web game (`wasm32`) and the SP1 program.
+#![no_std]
+
+extern crate alloc;
+
+pub mod commitment;
+pub mod deck;
+pub mod difficulty;
+pub mod engine;
+pub mod hex;
+pub mod replay_code;
+pub mod scoring;
+pub mod transcript;
@@ -0,0 +1,189 @@
+//! Shareable replay codes: a deck and the flips played on it, as short URL-safe text.
+//!
+//! ```text
+//! version u8 (= 1) | difficulty id u8 | seed u64 big-endian | flip count varint | flips | checksum
+//! ```
+//!
+//! Flips are bit-packed, most significant bit first, with just enough bits for the largest card
+//! index of the difficulty (4 to 6). The last byte is padded with zero bits. The checksum is the
+//! first 4 bytes of the SHA-256 of everything before it. The bytes are written as unpadded
+//! base64url, so a code fits in a URL fragment: a perfect expert game (36 flips) is 56 characters.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+use crate::difficulty::Difficulty;
+use crate::transcript::{write_varint, Reader};
+
+/// Version byte of the code format.
+pub const VERSION: u8 = 1;
+
+const CHECKSUM_LEN: usize = 4;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Why a replay code couldn't be made or read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeError {
+    /// A flip isn't a card index of the difficulty, so the game can't be encoded.
+    OutOfRange(u32),
+    /// Not base64url, too short, or the content doesn't parse.
+    Malformed,
+    /// Made by another version of the format.
+    Version(u8),
+    /// The checksum doesn't match, so the code was mistyped or cut off.
+    Checksum,
+}
+
+/// A deck and the flips played on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayCode {
+    pub difficulty: Difficulty,
+    pub seed: u64,
+    pub flips: Vec<u32>,
+}
+
+impl ReplayCode {
+    /// Encodes the code as base64url text. Every flip must be a card index of the difficulty.
+    pub fn encode(&self) -> Result<String, CodeError> {
+        let mut bytes = Vec::with_capacity(16 + self.flips.len());
+        bytes.push(VERSION);
+        bytes.push(self.difficulty.id());
+        bytes.extend_from_slice(&self.seed.to_be_bytes());
+        write_varint(&mut bytes, self.flips.len() as u64);
+
+        let bits = flip_bits(self.difficulty);
+        let cards = self.difficulty.pairs() * 2;
+        let mut buffer = 0u32;
+        let mut buffered = 0;
+        for &flip in &self.flips {
+            if flip >= cards {
+                return Err(CodeError::OutOfRange(flip));
+            }
+            buffer = (buffer << bits) | flip;
+            buffered += bits;
+            while buffered >= 8 {
+                buffered -= 8;
+                bytes.push((buffer >> buffered) as u8);
+            }
+            buffer &= (1 << buffered) - 1;
+        }
+        if buffered > 0 {
+            bytes.push((buffer << (8 - buffered)) as u8);
+        }
+
+        let checksum = Sha256::digest(&bytes);
+        bytes.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+        Ok(encode_base64url(&bytes))
+    }
+
+    /// Decodes base64url text. Flips must be card indices of the difficulty, but they aren't
+    /// checked against the game rules; replaying them does that.
+    pub fn decode(text: &str) -> Result<Self, CodeError> {
+        let bytes = decode_base64url(text).ok_or(CodeError::Malformed)?;
+        let (&version, _) = bytes.split_first().ok_or(CodeError::Malformed)?;
+        if version != VERSION {
+            return Err(CodeError::Version(version));
+        }
+        if bytes.len() < CHECKSUM_LEN + 1 {
+            return Err(CodeError::Malformed);
+        }
+
+        let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if Sha256::digest(content)[..CHECKSUM_LEN] != *checksum {
+            return Err(CodeError::Checksum);
+        }
+
+        let mut reader = Reader {
+            bytes: &content[1..],
+        };
+        let difficulty = reader
+            .byte()
+            .and_then(Difficulty::from_id)
+            .ok_or(CodeError::Malformed)?;
+        let seed = u64::from_be_bytes(reader.take::<8>().ok_or(CodeError::Malformed)?);
+        let count = reader.varint().ok_or(CodeError::Malformed)?;
+
+        // The packed flips must fill the rest exactly
+        let bits = flip_bits(difficulty);
+        let packed_len = count
+            .checked_mul(bits as u64)
+            .map(|total| total.div_ceil(8))
+            .ok_or(CodeError::Malformed)?;
+        if packed_len != reader.bytes.len() as u64 {
+            return Err(CodeError::Malformed);
+        }
+
+        let cards = difficulty.pairs() * 2;
+        let mut flips = Vec::with_capacity(count as usize);
+        let mut buffer = 0u32;
+        let mut buffered = 0;
+        for &byte in reader.bytes {
+            buffer = (buffer << 8) | byte as u32;
+            buffered += 8;
+            while buffered >= bits && flips.len() < count as usize {
+                buffered -= bits;
+                let flip = (buffer >> buffered) & ((1 << bits) - 1);
+                if flip >= cards {
+                    return Err(CodeError::Malformed);
+                }
+                flips.push(flip);
+            }
+            buffer &= (1 << buffered) - 1;
+        }
+        if buffer != 0 {
+            return Err(CodeError::Malformed); // Padding bits must be zero
+        }
+
+        Ok(Self {
+            difficulty,
+            seed,
+            flips,
+        })
+    }
+}
+
+// Bits needed for the largest card index of the difficulty
+fn flip_bits(difficulty: Difficulty) -> u32 {
+    let largest = difficulty.pairs() * 2 - 1;
+    u32::BITS - largest.leading_zeros()
+}
+
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |value, (index, &byte)| {
+                value | ((byte as u32) << (16 - 8 * index))
+            });
+        for index in 0..=chunk.len() {
+            text.push(ALPHABET[(value >> (18 - 6 * index)) as usize & 0x3f] as char);
+        }
+    }
+    text
+}
+
+fn decode_base64url(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    for &character in text.as_bytes() {
+        let sextet = ALPHABET.iter().position(|&letter| letter == character)? as u32;
+        buffer = (buffer << 6) | sextet;
+        buffered += 6;
+        if buffered >= 8 {
+            buffered -= 8;
+            bytes.push((buffer >> buffered) as u8);
+            buffer &= (1 << buffered) - 1;
+        }
+    }
+
+    // Leftover bits are padding: fewer than a byte, and all zero
+    if buffered >= 6 || buffer != 0 {
+        return None;
+    }
+    Some(bytes)
+}
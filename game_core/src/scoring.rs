@@ -0,0 +1,31 @@
+//! The scoring rule, in one place.
+//!
+//! The web game, the SP1 program and the proof script all score games through this module; the
+//! JavaScript side only displays scores computed here.
+
+use crate::difficulty::Difficulty;
+
+/// Seconds left on the clock after `elapsed` seconds (0 once the limit is reached).
+pub fn remaining_time(difficulty: Difficulty, elapsed: u32) -> u32 {
+    difficulty.time_limit().saturating_sub(elapsed)
+}
+
+/// Whether a game could have taken `time` seconds. The engine loses a game as soon as the clock
+/// reaches the limit, so a completed game ended before it, and no game runs past it.
+pub fn is_possible_time(difficulty: Difficulty, time: u32, complete: bool) -> bool {
+    if complete {
+        time < difficulty.time_limit()
+    } else {
+        time <= difficulty.time_limit()
+    }
+}
+
+/// Score of a game that took `time` seconds and `moves` moves: `Remaining Time - Moves`, clamped
+/// at 0. Games that weren't completed score 0.
+pub fn final_score(difficulty: Difficulty, moves: u32, time: u32, complete: bool) -> u32 {
+    if complete {
+        remaining_time(difficulty, time).saturating_sub(moves)
+    } else {
+        0
+    }
+}
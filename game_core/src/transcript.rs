@@ -0,0 +1,358 @@
+//! A timestamped log of everything that happened in a game, for replays and disputes.
+//!
+//! The web game records every deal, start, flip, pair resolution, timer tick, end and reset with
+//! the milliseconds since recording began. [`Transcript::last_game`] picks the latest game out of
+//! the log: its deck, its flips (the proof input) and its timings (for replays).
+//!
+//! The binary encoding is compact enough to store or send with a score:
+//!
+//! ```text
+//! version u8 (= 1) | started_at varint | entry count varint | entries
+//! entry: delay since the previous entry (ms) varint | tag u8 | operands
+//! ```
+//!
+//! Varints are unsigned LEB128. `Deal` operands are the difficulty id (u8) and the seed (u64,
+//! big-endian); every other operand is a varint.
+
+use alloc::vec::Vec;
+
+use crate::deck;
+use crate::difficulty::Difficulty;
+use crate::engine::{Command, Event, Game, Rejection};
+
+/// Version byte of the binary encoding.
+pub const VERSION: u8 = 1;
+
+/// Something that happened in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// A deck was dealt from `seed`. Games until the next deal are played on it.
+    Deal { difficulty: Difficulty, seed: u64 },
+    /// A game started on the dealt deck.
+    Start,
+    /// The card at `index` was flipped.
+    Flip { index: u32 },
+    /// The two face-up cards were resolved as a pair.
+    Match { first: u32, second: u32 },
+    /// The two face-up cards were resolved as different and turned back.
+    Mismatch { first: u32, second: u32 },
+    /// The timer reached `elapsed` seconds.
+    Tick { elapsed: u32 },
+    /// Every pair was matched.
+    Won,
+    /// The time limit was reached.
+    Lost,
+    /// The player reset the game (a new deal follows).
+    Reset,
+}
+
+impl Action {
+    /// The action behind an engine event.
+    pub fn from_event(event: &Event) -> Self {
+        match *event {
+            Event::Started => Action::Start,
+            Event::CardFlipped { index, .. } => Action::Flip {
+                index: index as u32,
+            },
+            Event::MatchFound { first, second, .. } => Action::Match {
+                first: first as u32,
+                second: second as u32,
+            },
+            Event::Mismatch { first, second } => Action::Mismatch {
+                first: first as u32,
+                second: second as u32,
+            },
+            Event::Tick { elapsed, .. } => Action::Tick { elapsed },
+            Event::Won { .. } => Action::Won,
+            Event::Lost => Action::Lost,
+        }
+    }
+
+    /// The engine command behind a recorded action: a match or mismatch is the `Resolve` that
+    /// produced it, and a loss is the `Tick` that ran out of time. Deals, wins and resets aren't
+    /// commands.
+    pub fn command(&self) -> Option<Command> {
+        match *self {
+            Action::Start => Some(Command::Start),
+            Action::Flip { index } => Some(Command::Flip(index as usize)),
+            Action::Match { .. } | Action::Mismatch { .. } => Some(Command::Resolve),
+            Action::Tick { .. } | Action::Lost => Some(Command::Tick),
+            Action::Deal { .. } | Action::Won | Action::Reset => None,
+        }
+    }
+
+    /// Short lowercase name (`"deal"`, `"flip"`, ...), as used in the JSON export.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Deal { .. } => "deal",
+            Action::Start => "start",
+            Action::Flip { .. } => "flip",
+            Action::Match { .. } => "match",
+            Action::Mismatch { .. } => "mismatch",
+            Action::Tick { .. } => "tick",
+            Action::Won => "won",
+            Action::Lost => "lost",
+            Action::Reset => "reset",
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            Action::Deal { .. } => 0,
+            Action::Start => 1,
+            Action::Flip { .. } => 2,
+            Action::Match { .. } => 3,
+            Action::Mismatch { .. } => 4,
+            Action::Tick { .. } => 5,
+            Action::Won => 6,
+            Action::Lost => 7,
+            Action::Reset => 8,
+        }
+    }
+}
+
+/// An action and when it happened, in milliseconds since recording began.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub at_ms: u64,
+    pub action: Action,
+}
+
+/// The log of a game board, in the order things happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    /// When recording began, in milliseconds since the Unix epoch.
+    pub started_at_ms: u64,
+    pub entries: Vec<Entry>,
+}
+
+/// The latest game of a transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedGame<'a> {
+    pub difficulty: Difficulty,
+    pub seed: u64,
+    /// Entries from the game's `Start` to the end of the transcript.
+    pub entries: &'a [Entry],
+}
+
+impl Transcript {
+    pub fn new(started_at_ms: u64) -> Self {
+        Self {
+            started_at_ms,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Appends an action. Timestamps never go backwards, so an earlier `at_ms` is clamped.
+    pub fn record(&mut self, at_ms: u64, action: Action) {
+        let at_ms = self
+            .entries
+            .last()
+            .map_or(at_ms, |last| at_ms.max(last.at_ms));
+        self.entries.push(Entry { at_ms, action });
+    }
+
+    /// The game started last and the deck it was played on, or `None` if the latest deal hasn't
+    /// been played.
+    pub fn last_game(&self) -> Option<RecordedGame<'_>> {
+        let start = self
+            .entries
+            .iter()
+            .rposition(|entry| matches!(entry.action, Action::Start | Action::Deal { .. }))?;
+        let (difficulty, seed) =
+            self.entries[..start]
+                .iter()
+                .rev()
+                .find_map(|entry| match entry.action {
+                    Action::Deal { difficulty, seed } => Some((difficulty, seed)),
+                    _ => None,
+                })?;
+
+        match self.entries[start].action {
+            Action::Start => Some(RecordedGame {
+                difficulty,
+                seed,
+                entries: &self.entries[start..],
+            }),
+            _ => None,
+        }
+    }
+
+    /// Encodes the transcript in the compact binary format. An entry earlier than the one before
+    /// it (only possible by editing `entries` directly) is encoded at that entry's time, as
+    /// [`Transcript::record`] would have clamped it.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16 + self.entries.len() * 3);
+        bytes.push(VERSION);
+        write_varint(&mut bytes, self.started_at_ms);
+        write_varint(&mut bytes, self.entries.len() as u64);
+
+        let mut previous = 0;
+        for entry in &self.entries {
+            write_varint(&mut bytes, entry.at_ms.saturating_sub(previous));
+            previous = previous.max(entry.at_ms);
+
+            bytes.push(entry.action.tag());
+            match entry.action {
+                Action::Deal { difficulty, seed } => {
+                    bytes.push(difficulty.id());
+                    bytes.extend_from_slice(&seed.to_be_bytes());
+                }
+                Action::Flip { index } => write_varint(&mut bytes, index as u64),
+                Action::Match { first, second } | Action::Mismatch { first, second } => {
+                    write_varint(&mut bytes, first as u64);
+                    write_varint(&mut bytes, second as u64);
+                }
+                Action::Tick { elapsed } => write_varint(&mut bytes, elapsed as u64),
+                Action::Start | Action::Won | Action::Lost | Action::Reset => {}
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes the binary format. Returns `None` for another version, unknown tags, truncated
+    /// input or trailing bytes.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader { bytes };
+        if reader.byte()? != VERSION {
+            return None;
+        }
+        let started_at_ms = reader.varint()?;
+        let count = reader.varint()?;
+
+        let mut transcript = Self::new(started_at_ms);
+        let mut at_ms = 0u64;
+        for _ in 0..count {
+            at_ms = at_ms.checked_add(reader.varint()?)?;
+            let action = match reader.byte()? {
+                0 => {
+                    let difficulty = Difficulty::from_id(reader.byte()?)?;
+                    let seed = u64::from_be_bytes(reader.take::<8>()?);
+                    Action::Deal { difficulty, seed }
+                }
+                1 => Action::Start,
+                2 => Action::Flip {
+                    index: reader.varint_u32()?,
+                },
+                3 => Action::Match {
+                    first: reader.varint_u32()?,
+                    second: reader.varint_u32()?,
+                },
+                4 => Action::Mismatch {
+                    first: reader.varint_u32()?,
+                    second: reader.varint_u32()?,
+                },
+                5 => Action::Tick {
+                    elapsed: reader.varint_u32()?,
+                },
+                6 => Action::Won,
+                7 => Action::Lost,
+                8 => Action::Reset,
+                _ => return None,
+            };
+            transcript.entries.push(Entry { at_ms, action });
+        }
+
+        if !reader.bytes.is_empty() {
+            return None;
+        }
+        Some(transcript)
+    }
+}
+
+/// Plays `entries` on the deck dealt from `seed`, the way a replay shows them. Fails with the
+/// index of the first entry the engine refuses, so a tampered transcript can't pass for a game.
+pub fn rebuild(
+    difficulty: Difficulty,
+    seed: u64,
+    entries: &[Entry],
+) -> Result<Game, (usize, Rejection)> {
+    let mut game = Game::new(difficulty, deck::shuffled_deck(seed, difficulty.pairs()));
+    for (index, entry) in entries.iter().enumerate() {
+        if let Some(command) = entry.action.command() {
+            game.apply(command)
+                .map_err(|rejection| (index, rejection))?;
+        }
+    }
+    Ok(game)
+}
+
+impl RecordedGame<'_> {
+    /// The game as it ended, rebuilt with [`rebuild`].
+    pub fn rebuild(&self) -> Result<Game, (usize, Rejection)> {
+        rebuild(self.difficulty, self.seed, self.entries)
+    }
+
+    /// Every card flip in order, as the SP1 program reads them.
+    pub fn flips(&self) -> Vec<u32> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry.action {
+                Action::Flip { index } => Some(index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Seconds on the game clock when the transcript ends (the time limit for a lost game).
+    pub fn time(&self) -> u32 {
+        self.entries
+            .iter()
+            .fold(0, |time, entry| match entry.action {
+                Action::Tick { elapsed } => elapsed,
+                Action::Lost => self.difficulty.time_limit(),
+                _ => time,
+            })
+    }
+}
+
+pub(crate) fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+pub(crate) struct Reader<'a> {
+    pub(crate) bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    pub(crate) fn byte(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        Some(byte)
+    }
+
+    pub(crate) fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        if self.bytes.len() < N {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        taken.try_into().ok()
+    }
+
+    pub(crate) fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            // The tenth group only has room for one bit
+            let group = (byte & 0x7f) as u64;
+            if (group << shift) >> shift != group {
+                return None;
+            }
+            value |= group << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn varint_u32(&mut self) -> Option<u32> {
+        self.varint()?.try_into().ok()
+    }
+}
@@ -0,0 +1,63 @@
+//! Commit-reveal deck sessions: the commitment binds the server seed and the difficulty, and the
+//! deck seed derivation is pinned so the browser, the service and the program agree on it.
+
+use memory_game_core::commitment;
+use memory_game_core::difficulty::Difficulty;
+
+const SERVER_SEED: [u8; 32] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31,
+];
+
+#[test]
+fn reveal_matches_its_commitment() {
+    for difficulty in Difficulty::ALL {
+        let commitment = commitment::commit(&SERVER_SEED, difficulty);
+        assert!(commitment::verify_reveal(
+            &commitment,
+            &SERVER_SEED,
+            difficulty
+        ));
+    }
+
+    // SHA-256(server seed || difficulty id)
+    assert_eq!(
+        memory_game_core::hex::encode(&commitment::commit(&SERVER_SEED, Difficulty::Expert)),
+        "944c533876f9de37bba870cd1bb4d10c91b022a459cbbca21c4c1745bfe244b4"
+    );
+}
+
+#[test]
+fn wrong_seed_or_difficulty_is_rejected() {
+    let commitment = commitment::commit(&SERVER_SEED, Difficulty::Expert);
+
+    let mut other_seed = SERVER_SEED;
+    other_seed[31] ^= 1;
+    assert!(!commitment::verify_reveal(
+        &commitment,
+        &other_seed,
+        Difficulty::Expert
+    ));
+
+    // The tier can't be changed after the deck is seen
+    for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+        assert!(!commitment::verify_reveal(
+            &commitment,
+            &SERVER_SEED,
+            difficulty
+        ));
+    }
+}
+
+#[test]
+fn deck_seed_is_pinned() {
+    // First 8 bytes (little-endian) of SHA-256(server seed || client seed little-endian)
+    assert_eq!(
+        commitment::deck_seed(&SERVER_SEED, 0x5eed_0f1a_9c3b_7d21),
+        0x3002_4356_b8b5_ca4e
+    );
+    assert_ne!(
+        commitment::deck_seed(&SERVER_SEED, 0x5eed_0f1a_9c3b_7d22),
+        commitment::deck_seed(&SERVER_SEED, 0x5eed_0f1a_9c3b_7d21)
+    );
+}
@@ -0,0 +1,6 @@
+//! Fixtures shared by the integration tests.
+
+/// Deck with pairs side by side: card `2k` and `2k + 1` match.
+pub fn sorted_deck(pairs: u32) -> Vec<u32> {
+    (0..pairs).flat_map(|value| [value, value]).collect()
+}
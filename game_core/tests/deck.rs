@@ -0,0 +1,52 @@
+//! The deck shuffle must give the same deck for the same seed in the browser, on the host and in
+//! the zkVM. The expected decks are pinned here, so any change to the generator or the shuffle
+//! (which would make old sessions unprovable) fails the build.
+
+use memory_game_core::deck::{self, DeckRng};
+use memory_game_core::difficulty::Difficulty;
+
+#[test]
+fn rng_is_splitmix64() {
+    // First outputs of the reference SplitMix64 for seed 0
+    let mut rng = DeckRng::new(0);
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+}
+
+#[test]
+fn shuffled_deck_is_pinned() {
+    assert_eq!(
+        deck::shuffled_deck(0x0123_4567_89ab_cdef, 8),
+        [1, 5, 3, 2, 6, 7, 7, 4, 2, 3, 5, 0, 4, 1, 6, 0]
+    );
+    assert_eq!(
+        deck::shuffled_deck(0, 6),
+        [2, 5, 3, 3, 1, 0, 1, 4, 4, 0, 2, 5]
+    );
+    assert_eq!(
+        deck::shuffled_deck(u64::MAX, 18),
+        [
+            7, 9, 5, 0, 4, 8, 11, 12, 13, 17, 6, 6, 8, 3, 5, 2, 14, 1, 1, 4, 17, 2, 9, 15, 10, 13,
+            0, 10, 16, 14, 12, 11, 7, 3, 15, 16
+        ]
+    );
+}
+
+#[test]
+fn shuffled_deck_has_every_pair_twice() {
+    for difficulty in Difficulty::ALL {
+        for seed in [0, 1, 42, 0xdead_beef, u64::MAX] {
+            let cards = deck::shuffled_deck(seed, difficulty.pairs());
+            assert_eq!(cards.len(), difficulty.pairs() as usize * 2);
+
+            let mut counts = vec![0; difficulty.pairs() as usize];
+            for &card in &cards {
+                counts[card as usize] += 1;
+            }
+            assert!(
+                counts.iter().all(|&count| count == 2),
+                "seed={seed} {difficulty:?}: {cards:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! The game state machine: which commands each phase accepts and where they lead.
+
+mod common;
+
+use common::sorted_deck;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::{Command, Event, Game, Phase, Rejection};
+
+const EASY: Difficulty = Difficulty::Easy;
+
+fn started_on(cards: Vec<u32>) -> Game {
+    let mut game = Game::new(EASY, cards);
+    assert_eq!(game.apply(Command::Start), Ok(vec![Event::Started]));
+    game
+}
+
+fn started() -> Game {
+    started_on(sorted_deck(EASY.pairs()))
+}
+
+#[test]
+fn flips_are_rejected_outside_playing() {
+    let mut game = Game::new(EASY, sorted_deck(EASY.pairs()));
+    assert_eq!(
+        game.apply(Command::Flip(0)),
+        Err(Rejection::WrongPhase(Phase::Idle))
+    );
+    assert_eq!(
+        game.apply(Command::Resolve),
+        Err(Rejection::WrongPhase(Phase::Idle))
+    );
+    assert_eq!(
+        game.apply(Command::Tick),
+        Err(Rejection::WrongPhase(Phase::Idle))
+    );
+
+    let mut game = started();
+    for _ in 0..EASY.time_limit() {
+        game.apply(Command::Tick).unwrap();
+    }
+    assert_eq!(game.phase(), Phase::Lost);
+    assert_eq!(
+        game.apply(Command::Flip(0)),
+        Err(Rejection::WrongPhase(Phase::Lost))
+    );
+    assert!(game.flips().is_empty());
+}
+
+#[test]
+fn bad_flips_leave_the_game_unchanged() {
+    let mut game = started();
+    let cards = game.cards().len();
+    assert_eq!(
+        game.apply(Command::Flip(cards)),
+        Err(Rejection::OutOfRange(cards))
+    );
+
+    game.apply(Command::Flip(0)).unwrap();
+    assert_eq!(
+        game.apply(Command::Flip(0)),
+        Err(Rejection::AlreadyFlipped(0))
+    );
+    game.apply(Command::Flip(1)).unwrap();
+    game.apply(Command::Resolve).unwrap();
+    assert_eq!(
+        game.apply(Command::Flip(1)),
+        Err(Rejection::AlreadyMatched(1))
+    );
+
+    assert_eq!(game.flips(), [0, 1]);
+    assert_eq!(game.moves(), 1);
+}
+
+#[test]
+fn third_flip_is_rejected_while_checking() {
+    let mut game = started();
+    game.apply(Command::Flip(0)).unwrap();
+    game.apply(Command::Flip(2)).unwrap();
+    assert_eq!(game.phase(), Phase::Checking);
+
+    assert_eq!(
+        game.apply(Command::Flip(4)),
+        Err(Rejection::WrongPhase(Phase::Checking))
+    );
+    assert_eq!(game.flips(), [0, 2]);
+    assert!(game.is_face_up(0) && game.is_face_up(2) && !game.is_face_up(4));
+}
+
+#[test]
+fn match_and_mismatch_resolve_back_to_playing() {
+    let mut game = started();
+
+    game.apply(Command::Flip(0)).unwrap();
+    game.apply(Command::Flip(2)).unwrap();
+    assert_eq!(
+        game.apply(Command::Resolve),
+        Ok(vec![Event::Mismatch {
+            first: 0,
+            second: 2
+        }])
+    );
+    assert_eq!(game.phase(), Phase::Playing);
+    assert!(!game.is_face_up(0) && !game.is_matched(0));
+
+    game.apply(Command::Flip(3)).unwrap();
+    game.apply(Command::Flip(2)).unwrap();
+    assert_eq!(
+        game.apply(Command::Resolve),
+        Ok(vec![Event::MatchFound {
+            first: 3,
+            second: 2,
+            value: 1
+        }])
+    );
+    assert_eq!(game.phase(), Phase::Playing);
+    assert!(game.is_matched(2) && game.is_matched(3));
+    assert_eq!(game.matched_pairs(), 1);
+    assert_eq!(game.moves(), 2);
+
+    // Nothing left to resolve
+    assert_eq!(
+        game.apply(Command::Resolve),
+        Err(Rejection::WrongPhase(Phase::Playing))
+    );
+}
+
+#[test]
+fn ticking_past_the_limit_loses() {
+    let mut game = started();
+    game.apply(Command::Flip(0)).unwrap();
+    game.apply(Command::Flip(2)).unwrap();
+
+    for second in 1..EASY.time_limit() {
+        assert_eq!(
+            game.apply(Command::Tick),
+            Ok(vec![Event::Tick {
+                elapsed: second,
+                remaining: EASY.time_limit() - second
+            }])
+        );
+    }
+    assert_eq!(game.apply(Command::Tick), Ok(vec![Event::Lost]));
+    assert_eq!(game.phase(), Phase::Lost);
+    assert!(
+        !game.is_face_up(0),
+        "face-up cards are cleared when time runs out"
+    );
+    assert_eq!(
+        game.apply(Command::Tick),
+        Err(Rejection::WrongPhase(Phase::Lost))
+    );
+    assert_eq!(game.score(), 0);
+}
+
+#[test]
+fn last_pair_wins() {
+    let mut game = started();
+    game.apply(Command::Tick).unwrap();
+
+    for pair in 0..EASY.pairs() as usize {
+        game.apply(Command::Flip(pair * 2)).unwrap();
+        game.apply(Command::Flip(pair * 2 + 1)).unwrap();
+        let events = game.apply(Command::Resolve).unwrap();
+
+        if pair + 1 < EASY.pairs() as usize {
+            assert_eq!(game.phase(), Phase::Playing);
+        } else {
+            let score = EASY.time_limit() - 1 - EASY.pairs();
+            assert_eq!(events.last(), Some(&Event::Won { score }));
+        }
+    }
+
+    assert_eq!(game.phase(), Phase::Won);
+    assert!(game.is_complete() && game.is_over());
+    assert_eq!(
+        game.apply(Command::Tick),
+        Err(Rejection::WrongPhase(Phase::Won))
+    );
+}
+
+#[test]
+fn a_deck_is_played_once() {
+    let mut game = started();
+    assert_eq!(
+        game.apply(Command::Start),
+        Err(Rejection::WrongPhase(Phase::Playing))
+    );
+
+    for _ in 0..EASY.time_limit() {
+        game.apply(Command::Tick).unwrap();
+    }
+    assert_eq!(
+        game.apply(Command::Start),
+        Err(Rejection::WrongPhase(Phase::Lost))
+    );
+
+    let cards = game.cards().to_vec();
+    let flips: Vec<u32> = (0..cards.len() as u32).collect();
+    let mut won = Game::replay(EASY, cards, &flips).unwrap();
+    assert_eq!(won.phase(), Phase::Won);
+    assert_eq!(
+        won.apply(Command::Start),
+        Err(Rejection::WrongPhase(Phase::Won))
+    );
+}
+
+#[test]
+fn replay_agrees_with_step_by_step_play() {
+    let cards = vec![2, 0, 1, 0, 5, 3, 2, 4, 1, 5, 3, 4];
+    let flips = [0, 1, 1, 3, 2, 8, 4, 9, 5, 10, 6, 0, 7, 11];
+
+    let mut live = started_on(cards.clone());
+    for &flip in &flips {
+        live.apply(Command::Flip(flip as usize)).unwrap();
+        if live.phase() == Phase::Checking {
+            live.apply(Command::Resolve).unwrap();
+        }
+    }
+
+    let replayed = Game::replay(EASY, cards.clone(), &flips).unwrap();
+    assert_eq!(replayed.phase(), Phase::Won);
+    assert_eq!(replayed.phase(), live.phase());
+    assert_eq!(replayed.moves(), live.moves());
+    assert_eq!(replayed.flips(), live.flips());
+    assert_eq!(replayed.score(), live.score());
+
+    // A flip the live game would refuse fails the replay too
+    assert_eq!(
+        Game::replay(EASY, cards, &[0, 0]).err(),
+        Some(Rejection::AlreadyFlipped(0))
+    );
+}
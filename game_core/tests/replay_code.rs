@@ -0,0 +1,69 @@
+//! Replay codes survive the round trip and refuse damaged text.
+
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::replay_code::{CodeError, ReplayCode};
+
+fn code(difficulty: Difficulty, flips: Vec<u32>) -> ReplayCode {
+    ReplayCode {
+        difficulty,
+        seed: 0x0123_4567_89ab_cdef,
+        flips,
+    }
+}
+
+#[test]
+fn codes_round_trip_for_every_difficulty() {
+    for difficulty in Difficulty::ALL {
+        let cards = difficulty.pairs() * 2;
+        for flips in [
+            vec![],
+            vec![0],
+            (0..cards).chain((0..cards).rev()).collect(),
+        ] {
+            let original = code(difficulty, flips);
+            let text = original.encode().unwrap();
+
+            assert!(text
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'));
+            assert_eq!(ReplayCode::decode(&text), Ok(original));
+        }
+    }
+}
+
+#[test]
+fn damaged_codes_are_refused() {
+    let text = code(Difficulty::Expert, vec![35, 0, 17, 4])
+        .encode()
+        .unwrap();
+
+    // One character changed
+    let mut typo = text.clone().into_bytes();
+    let last = typo.len() - 3;
+    typo[last] = if typo[last] == b'A' { b'B' } else { b'A' };
+    assert_eq!(
+        ReplayCode::decode(core::str::from_utf8(&typo).unwrap()),
+        Err(CodeError::Checksum)
+    );
+
+    // Cut off, or not base64url at all
+    assert!(ReplayCode::decode(&text[..text.len() - 4]).is_err());
+    assert_eq!(ReplayCode::decode("not a code!"), Err(CodeError::Malformed));
+    assert_eq!(ReplayCode::decode(""), Err(CodeError::Malformed));
+
+    // A later format version
+    assert_eq!(ReplayCode::decode("Ag"), Err(CodeError::Version(2)));
+}
+
+#[test]
+fn flips_off_the_board_are_not_encoded() {
+    // Easy has 12 cards: 12 would fit the 4-bit packing, but isn't a card
+    assert_eq!(
+        code(Difficulty::Easy, vec![0, 11, 12]).encode(),
+        Err(CodeError::OutOfRange(12))
+    );
+    assert_eq!(
+        code(Difficulty::Expert, vec![64]).encode(),
+        Err(CodeError::OutOfRange(64))
+    );
+}
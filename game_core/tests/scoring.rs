@@ -0,0 +1,182 @@
+//! Differential test for the scoring rule.
+//!
+//! Before `scoring` existed the rule was written out separately in the web game (`end_game`), the
+//! SP1 program, the proof script and the JavaScript bridge/backend. The copies below are those
+//! implementations verbatim (modulo types); every path that scores a game today must agree with
+//! all of them.
+
+// The legacy formulas are kept exactly as they were written
+#![allow(clippy::implicit_saturating_sub)]
+
+mod common;
+
+use common::sorted_deck;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::{Command, Game, Phase};
+use memory_game_core::scoring;
+
+/// The legacy code only knew the 8-pair, 120-second game.
+const NORMAL: Difficulty = Difficulty::Normal;
+const TIME_LIMIT: u32 = 120;
+
+/// `end_game` in the web game and `main` in the SP1 program: signed subtraction, clamped after.
+fn legacy_rust(moves: u32, time: u32, complete: bool) -> u32 {
+    let remaining_time = if time < TIME_LIMIT {
+        TIME_LIMIT - time
+    } else {
+        0
+    };
+    let score = if complete {
+        remaining_time as i32 - moves as i32
+    } else {
+        0
+    };
+    if score < 0 {
+        0
+    } else {
+        score as u32
+    }
+}
+
+/// `sp1-bridge.js` / `backend/server.js`: `Math.max(0, remaining_time - moves)` (always "complete").
+fn legacy_js(moves: u32, time: u32) -> u32 {
+    let remaining_time = if time < TIME_LIMIT {
+        TIME_LIMIT - time
+    } else {
+        0
+    } as f64;
+    f64::max(0.0, remaining_time - moves as f64) as u32
+}
+
+/// Plays a full game live: `mismatches` wrong guesses first, then every pair, ticking `time`
+/// seconds before the last pair is resolved.
+fn play_live(pairs: u32, mismatches: u32, time: u32) -> Game {
+    let mut game = Game::new(NORMAL, sorted_deck(pairs));
+    game.apply(Command::Start).unwrap();
+
+    for _ in 0..mismatches {
+        game.apply(Command::Flip(0)).unwrap();
+        game.apply(Command::Flip(2)).unwrap();
+        game.apply(Command::Resolve).unwrap();
+    }
+
+    for pair in 0..pairs as usize {
+        if pair + 1 == pairs as usize {
+            for _ in 0..time {
+                game.apply(Command::Tick).unwrap();
+            }
+        }
+        game.apply(Command::Flip(pair * 2)).unwrap();
+        game.apply(Command::Flip(pair * 2 + 1)).unwrap();
+        game.apply(Command::Resolve).unwrap();
+    }
+
+    game
+}
+
+#[test]
+fn normal_difficulty_is_the_legacy_game() {
+    assert_eq!(NORMAL.pairs(), 8);
+    assert_eq!(NORMAL.time_limit(), TIME_LIMIT);
+}
+
+#[test]
+fn final_score_matches_legacy_formulas() {
+    for moves in 0..=2 * TIME_LIMIT {
+        for time in 0..=2 * TIME_LIMIT {
+            let expected = legacy_rust(moves, time, true);
+            assert_eq!(
+                scoring::final_score(NORMAL, moves, time, true),
+                expected,
+                "moves={moves} time={time}"
+            );
+            assert_eq!(
+                legacy_js(moves, time),
+                expected,
+                "moves={moves} time={time}"
+            );
+
+            assert_eq!(
+                scoring::final_score(NORMAL, moves, time, false),
+                legacy_rust(moves, time, false)
+            );
+        }
+    }
+}
+
+#[test]
+fn remaining_time_matches_legacy_formula() {
+    for time in 0..=2 * TIME_LIMIT {
+        let expected = if time < TIME_LIMIT {
+            TIME_LIMIT - time
+        } else {
+            0
+        };
+        assert_eq!(scoring::remaining_time(NORMAL, time), expected);
+    }
+}
+
+#[test]
+fn web_engine_and_replay_scores_agree() {
+    for mismatches in [0, 1, 5, 40, 130] {
+        for time in [0, 1, 36, 119] {
+            // The web game scores the live engine
+            let live = play_live(NORMAL.pairs(), mismatches, time);
+            assert_eq!(live.phase(), Phase::Won);
+            let web_score = live.score();
+
+            // The SP1 program replays the recorded flips and scores with the reported time
+            let replayed = Game::replay(NORMAL, sorted_deck(8), live.flips()).unwrap();
+            let guest_score =
+                scoring::final_score(NORMAL, replayed.moves(), time, replayed.is_complete());
+
+            assert_eq!(replayed.moves(), live.moves());
+            assert_eq!(
+                web_score, guest_score,
+                "mismatches={mismatches} time={time}"
+            );
+            assert_eq!(web_score, legacy_rust(live.moves(), time, true));
+        }
+    }
+}
+
+#[test]
+fn lost_and_unfinished_games_score_zero() {
+    let mut game = Game::new(NORMAL, sorted_deck(8));
+    game.apply(Command::Start).unwrap();
+    game.apply(Command::Flip(0)).unwrap();
+    game.apply(Command::Flip(1)).unwrap();
+    game.apply(Command::Resolve).unwrap();
+    assert_eq!(game.score(), 0);
+
+    for _ in 0..TIME_LIMIT {
+        game.apply(Command::Tick).unwrap();
+    }
+    assert_eq!(game.phase(), Phase::Lost);
+    assert_eq!(game.score(), 0);
+
+    let replayed = Game::replay(NORMAL, sorted_deck(8), game.flips()).unwrap();
+    assert_eq!(
+        scoring::final_score(NORMAL, replayed.moves(), TIME_LIMIT, replayed.is_complete()),
+        0
+    );
+}
+
+#[test]
+fn a_win_must_end_before_the_time_limit() {
+    // The live engine loses on the tick that reaches the limit
+    let mut game = Game::new(NORMAL, sorted_deck(8));
+    game.apply(Command::Start).unwrap();
+    for _ in 0..TIME_LIMIT - 1 {
+        game.apply(Command::Tick).unwrap();
+    }
+    assert_eq!(game.phase(), Phase::Playing);
+    game.apply(Command::Tick).unwrap();
+    assert_eq!(game.phase(), Phase::Lost);
+
+    // So the SP1 program and the proving service only accept a win up to a second before it
+    assert!(scoring::is_possible_time(NORMAL, TIME_LIMIT - 1, true));
+    assert!(!scoring::is_possible_time(NORMAL, TIME_LIMIT, true));
+    assert!(scoring::is_possible_time(NORMAL, TIME_LIMIT, false));
+    assert!(!scoring::is_possible_time(NORMAL, TIME_LIMIT + 1, false));
+}
@@ -0,0 +1,159 @@
+//! Round trip of the transcript binary encoding and recovery of the latest game.
+
+use memory_game_core::deck;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::{Command, Game, Phase};
+use memory_game_core::transcript::{Action, Transcript};
+
+/// A session with an abandoned game on one deck, then a finished game on another.
+fn session() -> Transcript {
+    let mut transcript = Transcript::new(1_760_000_000_000);
+    let actions = [
+        (
+            0,
+            Action::Deal {
+                difficulty: Difficulty::Easy,
+                seed: 1,
+            },
+        ),
+        (900, Action::Start),
+        (1_500, Action::Flip { index: 0 }),
+        (1_700, Action::Reset),
+        (
+            1_700,
+            Action::Deal {
+                difficulty: Difficulty::Easy,
+                seed: u64::MAX,
+            },
+        ),
+        (2_000, Action::Start),
+        (2_400, Action::Flip { index: 3 }),
+        (2_900, Action::Flip { index: 5 }),
+        (3_000, Action::Tick { elapsed: 1 }),
+        (
+            3_900,
+            Action::Mismatch {
+                first: 3,
+                second: 5,
+            },
+        ),
+        (4_000, Action::Tick { elapsed: 2 }),
+        (4_100, Action::Lost),
+    ];
+    for (at_ms, action) in actions {
+        transcript.record(at_ms, action);
+    }
+    transcript
+}
+
+#[test]
+fn binary_encoding_round_trips() {
+    let transcript = session();
+    let bytes = transcript.encode();
+
+    assert_eq!(Transcript::decode(&bytes), Some(transcript));
+
+    // Truncated, extended or re-versioned input is refused
+    assert_eq!(Transcript::decode(&bytes[..bytes.len() - 1]), None);
+    assert_eq!(Transcript::decode(&[bytes.as_slice(), &[0]].concat()), None);
+    assert_eq!(Transcript::decode(&[&[2], &bytes[1..]].concat()), None);
+}
+
+#[test]
+fn last_game_is_the_latest_start_on_the_latest_deal() {
+    let transcript = session();
+    let game = transcript.last_game().expect("a game was started");
+
+    assert_eq!(game.seed, u64::MAX);
+    assert_eq!(game.flips(), vec![3, 5]);
+    assert_eq!(game.time(), Difficulty::Easy.time_limit());
+
+    // The flips replay on the recorded deck
+    let cards = deck::shuffled_deck(game.seed, game.difficulty.pairs());
+    assert!(Game::replay(game.difficulty, cards, &game.flips()).is_ok());
+
+    // A fresh deal has no game yet
+    let mut transcript = transcript;
+    transcript.record(5_000, Action::Reset);
+    transcript.record(
+        5_000,
+        Action::Deal {
+            difficulty: Difficulty::Hard,
+            seed: 7,
+        },
+    );
+    assert_eq!(transcript.last_game(), None);
+}
+
+#[test]
+fn out_of_order_entries_encode_clamped() {
+    let mut transcript = session();
+    transcript.entries[3].at_ms = 100;
+
+    let decoded = Transcript::decode(&transcript.encode()).expect("still decodes");
+    assert_eq!(decoded.entries[3].at_ms, decoded.entries[2].at_ms);
+    assert_eq!(decoded.entries.last(), transcript.entries.last());
+}
+
+#[test]
+fn varints_past_64_bits_are_refused() {
+    let max = [
+        1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0,
+    ];
+    assert_eq!(
+        Transcript::decode(&max).map(|transcript| transcript.started_at_ms),
+        Some(u64::MAX)
+    );
+
+    let overflow = [
+        1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02, 0,
+    ];
+    assert_eq!(Transcript::decode(&overflow), None);
+}
+
+#[test]
+fn rebuilt_game_matches_the_recorded_one() {
+    let (difficulty, seed) = (Difficulty::Normal, 0x5eed);
+    let cards = deck::shuffled_deck(seed, difficulty.pairs());
+
+    // Play live, recording every event like the web game: a miss, then every pair in order
+    let mut transcript = Transcript::new(0);
+    transcript.record(0, Action::Deal { difficulty, seed });
+    let mut live = Game::new(difficulty, cards.clone());
+    let mut commands = vec![Command::Start, Command::Tick];
+    let miss = (1..cards.len())
+        .find(|&index| cards[index] != cards[0])
+        .unwrap();
+    commands.extend([Command::Flip(0), Command::Flip(miss), Command::Resolve]);
+    for value in 0..difficulty.pairs() {
+        commands.push(Command::Tick);
+        for index in (0..cards.len()).filter(|&index| cards[index] == value) {
+            commands.push(Command::Flip(index));
+        }
+        commands.push(Command::Resolve);
+    }
+    for (step, command) in commands.into_iter().enumerate() {
+        for event in live.apply(command).unwrap() {
+            transcript.record(step as u64 * 100, Action::from_event(&event));
+        }
+    }
+    assert_eq!(live.phase(), Phase::Won);
+
+    // The replay viewer rebuilds the same game from the decoded transcript
+    let decoded = Transcript::decode(&transcript.encode()).unwrap();
+    let recorded = decoded.last_game().unwrap();
+    let rebuilt = recorded.rebuild().unwrap();
+
+    assert_eq!(rebuilt.phase(), live.phase());
+    assert_eq!(rebuilt.flips(), live.flips());
+    assert_eq!(rebuilt.moves(), live.moves());
+    assert_eq!(rebuilt.score(), live.score());
+    assert_eq!(recorded.flips(), live.flips());
+
+    // An edited transcript (resolving before the second flip) fails at the entry the engine
+    // refuses
+    let mut edited = recorded.entries.to_vec();
+    edited.swap(3, 4);
+    let (index, _) = memory_game_core::transcript::rebuild(difficulty, seed, &edited).unwrap_err();
+    assert_eq!(index, 3);
+}
@@ -0,0 +1,62 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use alloy_sol_types::SolType;
+use memory_proof_lib::{
+    decode_public_values, entries_root, vkey_digest, LeaderboardEntry, LeaderboardPublicValues,
+};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+
+pub fn main() {
+    // Read input data: the memory game vkey and the public values of every game proof. The proofs
+    // themselves are written to the stdin by the script and checked by `verify_sp1_proof`.
+    let game_vkey = sp1_zkvm::io::read::<[u32; 8]>();
+    let games = sp1_zkvm::io::read::<Vec<Vec<u8>>>();
+
+    let mut nonces = BTreeSet::new();
+    let mut issuer = None;
+    let mut entries = Vec::with_capacity(games.len());
+
+    for public_values in &games {
+        // Each game must have a compressed proof of the memory game program with these values
+        let public_values_digest = Sha256::digest(public_values);
+        sp1_zkvm::lib::verify::verify_sp1_proof(&game_vkey, &public_values_digest.into());
+
+        let game = match decode_public_values(public_values) {
+            Ok(game) => game,
+            Err(error) => panic!("invalid game public values: {}", error),
+        };
+
+        // A game counts once, however many times its proof is submitted
+        assert!(
+            nonces.insert(game.nonce),
+            "nonce {} is aggregated twice",
+            game.nonce
+        );
+
+        // A leaderboard only ranks games dealt by one backend
+        assert!(
+            *issuer.get_or_insert(game.issuer) == game.issuer,
+            "session {} was issued by another backend",
+            game.sessionId
+        );
+
+        entries.push(LeaderboardEntry {
+            player: game.player,
+            sessionId: game.sessionId,
+            difficulty: game.difficulty,
+            isComplete: game.isComplete,
+            score: game.score,
+        });
+    }
+
+    // Commit the leaderboard, ABI-encoded like the game public values
+    let public_values = LeaderboardPublicValues {
+        gameVkey: vkey_digest(&game_vkey).into(),
+        issuer: issuer.unwrap_or_default(),
+        entriesRoot: entries_root(&entries).into(),
+        entries,
+    };
+    sp1_zkvm::io::commit_slice(&LeaderboardPublicValues::abi_encode(&public_values));
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @notice The SP1 verifier gateway interface, as deployed by sp1-contracts.
+interface ISP1Verifier {
+    function verifyProof(bytes32 programVKey, bytes calldata publicValues, bytes calldata proofBytes)
+        external
+        view;
+}
+
+/// @notice The public values committed by the memory game program (`memory_proof/lib`).
+struct MemoryGamePublicValues {
+    uint32 moves;
+    uint32 time;
+    uint32 matchedPairs;
+    uint32 score;
+    bool isComplete;
+    uint8 difficulty;
+    bytes32 sessionId;
+    bytes32 nonce;
+    address player;
+    address issuer;
+}
+
+/// @title MemoryGameLeaderboard
+/// @notice Accepts SP1 proofs of memory games and credits each score to the player who signed the
+///         game. Only games dealt by the pinned session issuer (the proving service's session
+///         key) count. Every game carries a nonce issued with its session, and a nonce is accepted
+///         only once, so a proof can't be submitted again.
+contract MemoryGameLeaderboard {
+    /// @notice The address of the SP1 verifier contract.
+    address public immutable verifier;
+
+    /// @notice The verification key of the memory game program.
+    bytes32 public immutable memoryGameProgramVKey;
+
+    /// @notice The address that signs the sessions of accepted games.
+    address public immutable sessionIssuer;
+
+    /// @notice Nonces of games already on the leaderboard.
+    mapping(bytes32 => bool) public usedNonces;
+
+    /// @notice The best proven score of each player at each difficulty. Scores of different
+    ///         difficulties aren't comparable.
+    mapping(address => mapping(uint8 => uint32)) public bestScores;
+
+    event ScoreSubmitted(
+        address indexed player, bytes32 indexed nonce, bytes32 sessionId, uint8 difficulty, uint32 score
+    );
+
+    error NonceAlreadyUsed(bytes32 nonce);
+    error UnknownSessionIssuer(address issuer);
+
+    constructor(address _verifier, bytes32 _memoryGameProgramVKey, address _sessionIssuer) {
+        verifier = _verifier;
+        memoryGameProgramVKey = _memoryGameProgramVKey;
+        sessionIssuer = _sessionIssuer;
+    }
+
+    /// @notice Verifies a memory game proof and records its score.
+    /// @param _publicValues The ABI-encoded MemoryGamePublicValues.
+    /// @param _proofBytes The Groth16 or PLONK proof.
+    function submitScore(bytes calldata _publicValues, bytes calldata _proofBytes)
+        external
+        returns (MemoryGamePublicValues memory game)
+    {
+        ISP1Verifier(verifier).verifyProof(memoryGameProgramVKey, _publicValues, _proofBytes);
+        game = abi.decode(_publicValues, (MemoryGamePublicValues));
+
+        if (game.issuer != sessionIssuer) {
+            revert UnknownSessionIssuer(game.issuer);
+        }
+        if (usedNonces[game.nonce]) {
+            revert NonceAlreadyUsed(game.nonce);
+        }
+        usedNonces[game.nonce] = true;
+
+        if (game.score > bestScores[game.player][game.difficulty]) {
+            bestScores[game.player][game.difficulty] = game.score;
+        }
+        emit ScoreSubmitted(game.player, game.nonce, game.sessionId, game.difficulty, game.score);
+    }
+}
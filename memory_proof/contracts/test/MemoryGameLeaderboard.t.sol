@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier, MemoryGameLeaderboard, MemoryGamePublicValues} from "../src/MemoryGameLeaderboard.sol";
+
+/// @notice Stands in for the SP1 verifier gateway: a proof is valid when it is the program vkey.
+contract MockVerifier is ISP1Verifier {
+    error InvalidProof();
+
+    function verifyProof(bytes32 programVKey, bytes calldata, bytes calldata proofBytes) external pure {
+        if (keccak256(proofBytes) != keccak256(abi.encodePacked(programVKey))) {
+            revert InvalidProof();
+        }
+    }
+}
+
+contract MemoryGameLeaderboardTest {
+    bytes32 constant VKEY = bytes32(uint256(0x5eed));
+    address constant ISSUER = address(0x70997970C51812dc3A010C7d01b50e0d17dc79C8);
+    address constant PLAYER = address(0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266);
+
+    MemoryGameLeaderboard leaderboard;
+
+    function setUp() public {
+        leaderboard = new MemoryGameLeaderboard(address(new MockVerifier()), VKEY, ISSUER);
+    }
+
+    function game(bytes32 nonce, uint8 difficulty, uint32 score) internal pure returns (MemoryGamePublicValues memory) {
+        return MemoryGamePublicValues({
+            moves: 12,
+            time: 36,
+            matchedPairs: 8,
+            score: score,
+            isComplete: true,
+            difficulty: difficulty,
+            sessionId: keccak256(abi.encode(nonce)),
+            nonce: nonce,
+            player: PLAYER,
+            issuer: ISSUER
+        });
+    }
+
+    function submit(MemoryGamePublicValues memory values) internal returns (bytes memory reason) {
+        try leaderboard.submitScore(abi.encode(values), abi.encodePacked(VKEY)) {
+            return "";
+        } catch (bytes memory revertData) {
+            return revertData;
+        }
+    }
+
+    function testRecordsBestScorePerDifficulty() public {
+        require(submit(game("a", 1, 72)).length == 0, "normal game rejected");
+        require(submit(game("b", 1, 60)).length == 0, "lower normal game rejected");
+        require(submit(game("c", 3, 40)).length == 0, "expert game rejected");
+
+        require(leaderboard.bestScores(PLAYER, 1) == 72, "a lower score replaced the best");
+        require(leaderboard.bestScores(PLAYER, 3) == 40, "expert score not recorded on its own");
+        require(leaderboard.bestScores(PLAYER, 0) == 0, "easy score recorded");
+        require(leaderboard.usedNonces("b"), "nonce not marked used");
+    }
+
+    function testRejectsReusedNonce() public {
+        require(submit(game("a", 1, 72)).length == 0, "game rejected");
+
+        bytes memory reason = submit(game("a", 2, 90));
+        require(
+            keccak256(reason)
+                == keccak256(abi.encodeWithSelector(MemoryGameLeaderboard.NonceAlreadyUsed.selector, bytes32("a"))),
+            "reused nonce accepted"
+        );
+        require(leaderboard.bestScores(PLAYER, 2) == 0, "score of a reused nonce recorded");
+    }
+
+    function testRejectsSessionsOfAnotherIssuer() public {
+        MemoryGamePublicValues memory values = game("a", 1, 72);
+        values.issuer = address(0xbad);
+
+        bytes memory reason = submit(values);
+        require(
+            keccak256(reason)
+                == keccak256(abi.encodeWithSelector(MemoryGameLeaderboard.UnknownSessionIssuer.selector, address(0xbad))),
+            "game of another issuer accepted"
+        );
+        require(!leaderboard.usedNonces("a"), "nonce of a rejected game used up");
+    }
+
+    function testRejectsInvalidProof() public {
+        bytes memory reason;
+        try leaderboard.submitScore(abi.encode(game("a", 1, 72)), hex"00") {} catch (bytes memory revertData) {
+            reason = revertData;
+        }
+        require(
+            keccak256(reason) == keccak256(abi.encodeWithSelector(MockVerifier.InvalidProof.selector)),
+            "invalid proof accepted"
+        );
+        require(leaderboard.bestScores(PLAYER, 1) == 0, "score of an invalid proof recorded");
+    }
+}
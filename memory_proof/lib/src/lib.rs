@@ -1,22 +1,172 @@
-use alloy_sol_types::sol;
+use alloy_sol_types::{sol, SolType};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use memory_game_core::difficulty::Difficulty;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 
 sol! {
-    /// The public values encoded as a struct that can be easily deserialized inside Solidity.
-    struct PublicValuesStruct {
-        uint32 n;
-        uint32 a;
-        uint32 b;
+    /// The public values of a memory game proof, encoded as a struct that can be easily
+    /// deserialized inside Solidity.
+    struct MemoryGamePublicValues {
+        uint32 moves;
+        uint32 time;
+        uint32 matchedPairs;
+        uint32 score;
+        bool isComplete;
+        uint8 difficulty;
+        bytes32 sessionId;
+        bytes32 nonce;
+        address player;
+        address issuer;
+    }
+
+    /// What the backend signs when it opens a session, so a proof can only be made of a deck the
+    /// backend dealt.
+    struct SessionTicket {
+        bytes32 sessionId;
+        bytes32 nonce;
+        uint8 difficulty;
+    }
+
+    /// What a player signs to claim a game: the session and everything the program replays.
+    struct GameTranscript {
+        bytes32 sessionId;
+        bytes32 nonce;
+        uint64 clientSeed;
+        uint8 difficulty;
+        uint32[] flips;
+        uint32 time;
+    }
+
+    /// One game on an aggregated leaderboard. Scores are only ranked against games of the same
+    /// difficulty.
+    struct LeaderboardEntry {
+        address player;
+        bytes32 sessionId;
+        uint8 difficulty;
+        bool isComplete;
+        uint32 score;
+    }
+
+    /// The public values of a leaderboard proof: every game the aggregation program verified
+    /// against `gameVkey`, all dealt by `issuer`, and the Merkle root of those entries.
+    struct LeaderboardPublicValues {
+        bytes32 gameVkey;
+        address issuer;
+        bytes32 entriesRoot;
+        LeaderboardEntry[] entries;
+    }
+}
+
+impl MemoryGamePublicValues {
+    /// The difficulty preset the transcript was replayed with.
+    pub fn difficulty_preset(&self) -> Option<Difficulty> {
+        Difficulty::from_id(self.difficulty)
+    }
+}
+
+impl GameTranscript {
+    /// Keccak-256 of the ABI-encoded transcript. Players sign it with `personal_sign`.
+    pub fn hash(&self) -> [u8; 32] {
+        Keccak256::digest(Self::abi_encode(self)).into()
+    }
+}
+
+impl SessionTicket {
+    /// Keccak-256 of the ABI-encoded ticket. The backend signs it with `personal_sign`.
+    pub fn hash(&self) -> [u8; 32] {
+        Keccak256::digest(Self::abi_encode(self)).into()
     }
 }
 
-/// Compute the n'th fibonacci number (wrapping around on overflows), using normal Rust code.
-pub fn fibonacci(n: u32) -> (u32, u32) {
-    let mut a = 0u32;
-    let mut b = 1u32;
-    for _ in 0..n {
-        let c = a.wrapping_add(b);
-        a = b;
-        b = c;
+// The digest `personal_sign` signs for a 32-byte message
+fn personal_message(hash: &[u8; 32]) -> [u8; 32] {
+    Keccak256::new()
+        .chain_update(b"\x19Ethereum Signed Message:\n32")
+        .chain_update(hash)
+        .finalize()
+        .into()
+}
+
+/// The Ethereum address of a public key: the last 20 bytes of the Keccak-256 of the uncompressed
+/// point.
+pub fn address_of(key: &VerifyingKey) -> [u8; 20] {
+    let point = key.to_encoded_point(false);
+    let digest = Keccak256::digest(&point.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&digest[12..]);
+    address
+}
+
+/// Signs `hash` the way `personal_sign` (EIP-191) does, as a 65-byte `r || s || v` signature
+/// with `v` 27 or 28. [`recover_signer`] gives back the key's address.
+pub fn sign_hash(hash: &[u8; 32], key: &SigningKey) -> [u8; 65] {
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(&personal_message(hash))
+        .expect("a 32-byte prehash can always be signed");
+    let mut bytes = [0u8; 65];
+    bytes[..64].copy_from_slice(&signature.to_bytes());
+    bytes[64] = recovery_id.to_byte() + 27;
+    bytes
+}
+
+/// Recovers the Ethereum address that signed `hash` with `personal_sign` (EIP-191), from a
+/// 65-byte `r || s || v` signature. `v` may be 0/1 or 27/28.
+pub fn recover_signer(hash: &[u8; 32], signature: &[u8; 65]) -> Option<[u8; 20]> {
+    let recovery_id =
+        RecoveryId::from_byte(signature[64].checked_sub(27).unwrap_or(signature[64]))?;
+    let signature = Signature::from_slice(&signature[..64]).ok()?;
+    let key = VerifyingKey::recover_from_prehash(&personal_message(hash), &signature, recovery_id)
+        .ok()?;
+    Some(address_of(&key))
+}
+
+/// Decode the ABI-encoded public values committed by the memory game program.
+pub fn decode_public_values(
+    bytes: &[u8],
+) -> Result<MemoryGamePublicValues, alloy_sol_types::Error> {
+    MemoryGamePublicValues::abi_decode(bytes, true)
+}
+
+/// Decode the ABI-encoded public values committed by the aggregation program.
+pub fn decode_leaderboard_values(
+    bytes: &[u8],
+) -> Result<LeaderboardPublicValues, alloy_sol_types::Error> {
+    LeaderboardPublicValues::abi_decode(bytes, true)
+}
+
+/// The game vkey digest as the aggregation program commits it: the eight words of
+/// `SP1VerifyingKey::hash_u32`, big-endian.
+pub fn vkey_digest(words: &[u32; 8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// Merkle root of leaderboard entries. Leaves are the SHA-256 of each ABI-encoded entry, parents
+/// the SHA-256 of both children, and an odd node is carried up unchanged. No entries give zeroes.
+pub fn entries_root(entries: &[LeaderboardEntry]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = entries
+        .iter()
+        .map(|entry| Sha256::digest(LeaderboardEntry::abi_encode(entry)).into())
+        .collect();
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => Sha256::new()
+                    .chain_update(left)
+                    .chain_update(right)
+                    .finalize()
+                    .into(),
+                [node] => *node,
+                _ => unreachable!(),
+            })
+            .collect();
     }
-    (a, b)
+
+    level.first().copied().unwrap_or([0u8; 32])
 }
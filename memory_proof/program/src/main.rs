@@ -1,43 +1,113 @@
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
-use serde::{Deserialize, Serialize};
-
-// Game data structure
-#[derive(Serialize, Deserialize)]
-struct GameData {
-    moves: u32,
-    time: u32,
-    matched_pairs: u32,
-}
+use alloy_sol_types::SolType;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::Game;
+use memory_game_core::{commitment, deck, scoring};
+use memory_proof_lib::{recover_signer, GameTranscript, MemoryGamePublicValues, SessionTicket};
 
 pub fn main() {
-    // Read input data
-    let moves = sp1_zkvm::io::read::<u32>();
+    // Read input data: the session commitment, the session nonce, both seeds, the difficulty, the ordered card flips, the elapsed time,
+    // the player's address with their signature over the transcript, and the backend's signature over the session ticket
+    let session_commitment = sp1_zkvm::io::read::<[u8; 32]>();
+    let nonce = sp1_zkvm::io::read::<[u8; 32]>();
+    let server_seed = sp1_zkvm::io::read::<[u8; 32]>();
+    let client_seed = sp1_zkvm::io::read::<u64>();
+    let difficulty_id = sp1_zkvm::io::read::<u8>();
+    let flips = sp1_zkvm::io::read::<Vec<u32>>();
     let time = sp1_zkvm::io::read::<u32>();
-    let matched_pairs = sp1_zkvm::io::read::<u32>();
-    
-    // Constants
-    const TIME_LIMIT: u32 = 120;
-    
+    let player = sp1_zkvm::io::read::<[u8; 20]>();
+    let signature = sp1_zkvm::io::read::<Vec<u8>>();
+    let session_signature = sp1_zkvm::io::read::<Vec<u8>>();
+
+    let difficulty = match Difficulty::from_id(difficulty_id) {
+        Some(difficulty) => difficulty,
+        None => panic!("unknown difficulty id: {}", difficulty_id),
+    };
+
+    // The revealed server seed and the difficulty must be the ones committed to when the session
+    // started
+    assert!(
+        commitment::verify_reveal(&session_commitment, &server_seed, difficulty),
+        "server seed or difficulty does not match the session commitment"
+    );
+
+    // The backend signed the session when it dealt it. Its address is committed, so verifiers
+    // only accept games from a backend they trust
+    let ticket = SessionTicket {
+        sessionId: session_commitment.into(),
+        nonce: nonce.into(),
+        difficulty: difficulty_id,
+    };
+    let session_signature: [u8; 65] = match session_signature.try_into() {
+        Ok(signature) => signature,
+        Err(_) => panic!("session signature must be 65 bytes"),
+    };
+    let issuer = match recover_signer(&ticket.hash(), &session_signature) {
+        Some(issuer) => issuer,
+        None => panic!("invalid session signature"),
+    };
+
+    // The player must have signed this exact game, so the score can't be claimed by anyone else
+    let transcript = GameTranscript {
+        sessionId: session_commitment.into(),
+        nonce: nonce.into(),
+        clientSeed: client_seed,
+        difficulty: difficulty_id,
+        flips: flips.clone(),
+        time,
+    };
+    let signature: [u8; 65] = match signature.try_into() {
+        Ok(signature) => signature,
+        Err(_) => panic!("player signature must be 65 bytes"),
+    };
+    assert!(
+        recover_signer(&transcript.hash(), &signature) == Some(player),
+        "transcript is not signed by the player"
+    );
+
+    // Rebuild the exact deck the player saw
+    let seed = commitment::deck_seed(&server_seed, client_seed);
+    let cards = deck::shuffled_deck(seed, difficulty.pairs());
+
+    // Re-run the game from the transcript with the same engine the web game uses
+    let game = match Game::replay(difficulty, cards, &flips) {
+        Ok(game) => game,
+        Err(rejection) => panic!("invalid transcript: {:?}", rejection),
+    };
+    let moves = game.moves();
+    let matched_pairs = game.matched_pairs();
+
     // Game validity check
-    let is_complete = matched_pairs == 8;
-    
-    // Calculate score: Remaining Time - Moves
-    let remaining_time = if time < TIME_LIMIT { TIME_LIMIT - time } else { 0 };
-    let score = if is_complete { 
-        remaining_time as i32 - moves as i32 
-    } else { 
-        0 
-    };
-    
-    // Set negative scores to zero
-    let final_score = if score < 0 { 0 } else { score as u32 };
-    
-    // Commit calculated values (verifiable outputs of the proof)
-    sp1_zkvm::io::commit(&moves);
-    sp1_zkvm::io::commit(&time);
-    sp1_zkvm::io::commit(&matched_pairs);
-    sp1_zkvm::io::commit(&final_score);
-    sp1_zkvm::io::commit(&is_complete);
-}
\ No newline at end of file
+    let is_complete = game.is_complete();
+
+    // The time is reported by the player's browser; a won game ended before the limit, like the
+    // engine decides it live
+    assert!(
+        scoring::is_possible_time(difficulty, time, is_complete),
+        "time {} is not possible for this game with a {} second limit",
+        time,
+        difficulty.time_limit()
+    );
+
+    // Calculate score: Remaining Time - Moves (negative scores are set to zero)
+    let final_score = scoring::final_score(difficulty, moves, time, is_complete);
+
+    // Commit calculated values (verifiable outputs of the proof), ABI-encoded so Solidity
+    // contracts and the proof tools decode them the same way. The nonce lets verifiers refuse a
+    // proof they have already accepted.
+    let public_values = MemoryGamePublicValues {
+        moves,
+        time,
+        matchedPairs: matched_pairs,
+        score: final_score,
+        isComplete: is_complete,
+        difficulty: difficulty.id(),
+        sessionId: session_commitment.into(),
+        nonce: nonce.into(),
+        player: player.into(),
+        issuer: issuer.into(),
+    };
+    sp1_zkvm::io::commit_slice(&MemoryGamePublicValues::abi_encode(&public_values));
+}
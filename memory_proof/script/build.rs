@@ -1,4 +1,5 @@
 fn main() {
-    // Build the program directory
+    // Build the game program and the program that aggregates its proofs
     sp1_build::build_program("../program");
+    sp1_build::build_program("../aggregation");
 }
\ No newline at end of file
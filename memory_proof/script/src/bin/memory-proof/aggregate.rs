@@ -0,0 +1,224 @@
+//! `aggregate`: prove every game of a JSONL file as a compressed proof, then prove them all at once
+//! with the aggregation program, which commits one leaderboard for the whole tournament.
+
+use super::batch::read_lines;
+use super::ProofSystem;
+use clap::Args;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::hex;
+use memory_proof_lib::{decode_leaderboard_values, vkey_digest};
+use memory_proof_script::{save_vkey, GameInput, AGGREGATION_ELF, MEMORY_GAME_ELF};
+use serde::Serialize;
+use sp1_sdk::{
+    EnvProver, HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin,
+};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct AggregateArgs {
+    /// Game inputs, one JSON object per line (the same shape as a `--input` file).
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Wrap the leaderboard proof for on-chain verification. Without it the proof is compressed.
+    #[clap(long, value_enum)]
+    system: Option<ProofSystem>,
+
+    /// Where to save the leaderboard proof.
+    #[clap(long, default_value = "leaderboard_proof.bin")]
+    proof: PathBuf,
+
+    /// Where to save the aggregation program verifying key.
+    #[clap(long, default_value = "leaderboard_vkey.bin")]
+    vkey: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateReport {
+    input_file: PathBuf,
+    games: usize,
+    aggregated: usize,
+    skipped: Vec<SkippedGame>,
+    system: &'static str,
+    game_vkey: String,
+    issuer: String,
+    entries_root: String,
+    leaderboard: Vec<LeaderboardRow>,
+    timings: AggregateTimings,
+    vkey_hash: String,
+    proof_file: PathBuf,
+    vkey_file: PathBuf,
+}
+
+/// A game left off the leaderboard, with its 1-based line in the input file.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SkippedGame {
+    line: usize,
+    error: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaderboardRow {
+    player: String,
+    session_id: String,
+    difficulty: String,
+    is_complete: bool,
+    score: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AggregateTimings {
+    games_ms: u64,
+    aggregate_ms: u64,
+    verify_ms: u64,
+}
+
+pub fn aggregate(client: &EnvProver, args: AggregateArgs) -> Result<AggregateReport, String> {
+    let games = read_lines(&args.input)?;
+
+    let (game_pk, game_vk) = client.setup(MEMORY_GAME_ELF);
+    let (pk, vk) = client.setup(AGGREGATION_ELF);
+
+    // Prove each game on its own; one that fails is left off rather than failing the tournament
+    let start = Instant::now();
+    let mut nonces = HashSet::new();
+    let mut issuer = None;
+    let mut proofs = Vec::new();
+    let mut skipped = Vec::new();
+    for (line, json) in &games {
+        match prove_compressed(client, &game_pk, json, &mut nonces, &mut issuer) {
+            Ok(proof) => {
+                eprintln!("line {}: proved", line);
+                proofs.push(proof);
+            }
+            Err(error) => {
+                eprintln!("line {}: {}", line, error);
+                skipped.push(SkippedGame { line: *line, error });
+            }
+        }
+    }
+    let games_ms = start.elapsed().as_millis() as u64;
+
+    if proofs.is_empty() {
+        return Err("No game in the input could be proved".to_string());
+    }
+    let aggregated = proofs.len();
+
+    // The aggregation program reads the game vkey and every game's public values, and checks
+    // them against the proofs written after them
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&game_vk.hash_u32());
+    stdin.write(
+        &proofs
+            .iter()
+            .map(|proof| proof.public_values.to_vec())
+            .collect::<Vec<_>>(),
+    );
+    for proof in proofs {
+        let SP1Proof::Compressed(proof) = proof.proof else {
+            return Err("Game proofs must be compressed to be aggregated".to_string());
+        };
+        stdin.write_proof(*proof, game_vk.vk.clone());
+    }
+
+    eprintln!("Aggregating {} games...", aggregated);
+    let start = Instant::now();
+    let proof = match args.system {
+        None => client.prove(&pk, &stdin).compressed().run(),
+        Some(ProofSystem::Plonk) => client.prove(&pk, &stdin).plonk().run(),
+        Some(ProofSystem::Groth16) => client.prove(&pk, &stdin).groth16().run(),
+    }
+    .map_err(|error| format!("Aggregation failed: {}", error))?;
+    let aggregate_ms = start.elapsed().as_millis() as u64;
+
+    let start = Instant::now();
+    client
+        .verify(&proof, &vk)
+        .map_err(|error| format!("Verification failed: {}", error))?;
+    let verify_ms = start.elapsed().as_millis() as u64;
+
+    proof
+        .save(&args.proof)
+        .map_err(|error| format!("Failed to save proof: {}", error))?;
+    save_vkey(&vk, &args.vkey)?;
+
+    let values = decode_leaderboard_values(proof.public_values.as_slice())
+        .map_err(|error| format!("Failed to decode leaderboard: {}", error))?;
+    if values.gameVkey.0 != vkey_digest(&game_vk.hash_u32()) {
+        return Err("Leaderboard was aggregated against another game vkey".to_string());
+    }
+
+    Ok(AggregateReport {
+        input_file: args.input,
+        games: games.len(),
+        aggregated,
+        skipped,
+        system: match args.system {
+            None => "compressed",
+            Some(ProofSystem::Plonk) => "plonk",
+            Some(ProofSystem::Groth16) => "groth16",
+        },
+        game_vkey: format!("0x{}", hex::encode(values.gameVkey.as_slice())),
+        issuer: format!("0x{}", hex::encode(values.issuer.as_slice())),
+        entries_root: format!("0x{}", hex::encode(values.entriesRoot.as_slice())),
+        leaderboard: values
+            .entries
+            .iter()
+            .map(|entry| LeaderboardRow {
+                player: format!("0x{}", hex::encode(entry.player.as_slice())),
+                session_id: hex::encode(entry.sessionId.as_slice()),
+                difficulty: Difficulty::from_id(entry.difficulty).map_or_else(
+                    || entry.difficulty.to_string(),
+                    |difficulty| difficulty.name().to_string(),
+                ),
+                is_complete: entry.isComplete,
+                score: entry.score,
+            })
+            .collect(),
+        timings: AggregateTimings {
+            games_ms,
+            aggregate_ms,
+            verify_ms,
+        },
+        vkey_hash: vk.bytes32(),
+        proof_file: args.proof,
+        vkey_file: args.vkey,
+    })
+}
+
+// A compressed proof of one game, which the aggregation program can verify. The program rejects
+// a nonce it has already seen, or a session another backend issued than the first game's, so
+// those are skipped here before paying for their proof.
+fn prove_compressed(
+    client: &EnvProver,
+    pk: &SP1ProvingKey,
+    json: &str,
+    nonces: &mut HashSet<[u8; 32]>,
+    issuer: &mut Option<[u8; 20]>,
+) -> Result<SP1ProofWithPublicValues, String> {
+    let input = GameInput::from_json(json)?;
+    input.check()?;
+
+    if nonces.contains(&input.nonce) {
+        return Err("Nonce is already on the leaderboard".to_string());
+    }
+    let game_issuer = input.issuer().ok_or("Session is not signed by a backend")?;
+    if issuer.is_some_and(|issuer| issuer != game_issuer) {
+        return Err("Session was issued by another backend than the leaderboard's".to_string());
+    }
+
+    let proof = client
+        .prove(pk, &input.stdin())
+        .compressed()
+        .run()
+        .map_err(|error| format!("Proving failed: {}", error))?;
+    nonces.insert(input.nonce);
+    *issuer = Some(game_issuer);
+    Ok(proof)
+}
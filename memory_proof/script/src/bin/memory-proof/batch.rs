@@ -0,0 +1,210 @@
+//! `batch`: prove every game of a JSONL file with one prover setup, a few games at a time, and
+//! write one JSONL result line per game.
+
+use clap::Args;
+use memory_proof_script::{
+    prove_game, GameInput, GameInputSummary, GamePublicValues, ProofDigests, ProofKind,
+    ProofTimings, MEMORY_GAME_ELF,
+};
+use serde::Serialize;
+use sp1_sdk::{EnvProver, SP1ProvingKey, SP1VerifyingKey};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// Game inputs, one JSON object per line (the same shape as a `--input` file).
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Where to write the results, one JSON object per input line.
+    #[clap(long, default_value = "batch-results.jsonl")]
+    output: PathBuf,
+
+    /// Games proved at the same time.
+    #[clap(long, default_value = "1")]
+    parallelism: usize,
+
+    /// Also save each proof as `<dir>/line-<n>.bin`.
+    #[clap(long)]
+    proof_dir: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReport {
+    input_file: PathBuf,
+    output_file: PathBuf,
+    parallelism: usize,
+    games: usize,
+    proved: usize,
+    failed: usize,
+    total_ms: u64,
+}
+
+/// One line of the results file. Lines are written as games finish, so they are not in input
+/// order; `line` is the 1-based line of the game in the input file.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchResult {
+    line: usize,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<GameInputSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_values: Option<GamePublicValues>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cycles: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timings: Option<ProofTimings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    digests: Option<ProofDigests>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl BatchResult {
+    fn failed(line: usize, input: Option<GameInputSummary>, error: String) -> Self {
+        Self {
+            line,
+            success: false,
+            input,
+            public_values: None,
+            cycles: None,
+            timings: None,
+            digests: None,
+            proof_file: None,
+            error: Some(error),
+        }
+    }
+}
+
+pub fn batch(client: &EnvProver, args: BatchArgs) -> Result<BatchReport, String> {
+    let games = read_lines(&args.input)?;
+    let parallelism = args.parallelism.max(1);
+
+    if let Some(dir) = &args.proof_dir {
+        std::fs::create_dir_all(dir)
+            .map_err(|error| format!("Failed to create {}: {}", dir.display(), error))?;
+    }
+    let output = File::create(&args.output)
+        .map_err(|error| format!("Failed to create {}: {}", args.output.display(), error))?;
+    let output = Mutex::new(BufWriter::new(output));
+
+    let start = Instant::now();
+    let (pk, vk) = client.setup(MEMORY_GAME_ELF);
+
+    // Each worker takes the next game until there are none left
+    let next = AtomicUsize::new(0);
+    let proved = AtomicUsize::new(0);
+    let worker = || -> Result<(), String> {
+        while let Some((line, json)) = games.get(next.fetch_add(1, Ordering::Relaxed)) {
+            let result = prove_line(client, &pk, &vk, *line, json, args.proof_dir.as_deref());
+            if result.success {
+                proved.fetch_add(1, Ordering::Relaxed);
+            }
+            eprintln!(
+                "line {}: {}",
+                line,
+                result.error.as_deref().unwrap_or("proved")
+            );
+
+            let mut output = output.lock().unwrap();
+            writeln!(output, "{}", serde_json::to_string(&result).unwrap())
+                .and_then(|_| output.flush())
+                .map_err(|error| format!("Failed to write {}: {}", args.output.display(), error))?;
+        }
+        Ok(())
+    };
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..parallelism).map(|_| scope.spawn(worker)).collect();
+        workers
+            .into_iter()
+            .try_for_each(|handle| handle.join().unwrap())
+    })?;
+
+    let proved = proved.into_inner();
+    Ok(BatchReport {
+        input_file: args.input,
+        output_file: args.output,
+        parallelism,
+        games: games.len(),
+        proved,
+        failed: games.len() - proved,
+        total_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Non-empty lines of a JSONL file with their 1-based line numbers.
+pub fn read_lines(path: &Path) -> Result<Vec<(usize, String)>, String> {
+    let file = File::open(path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+    let mut games = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+        if !line.trim().is_empty() {
+            games.push((index + 1, line));
+        }
+    }
+    Ok(games)
+}
+
+fn prove_line(
+    client: &EnvProver,
+    pk: &SP1ProvingKey,
+    vk: &SP1VerifyingKey,
+    line: usize,
+    json: &str,
+    proof_dir: Option<&Path>,
+) -> BatchResult {
+    let input = match GameInput::from_json(json) {
+        Ok(input) => input,
+        Err(error) => return BatchResult::failed(line, None, error),
+    };
+    let summary = input.summary();
+
+    let proven = match input
+        .check()
+        .and_then(|_| prove_game(client, pk, vk, &input, ProofKind::Core, |_| {}))
+    {
+        Ok(proven) => proven,
+        Err(error) => return BatchResult::failed(line, Some(summary), error),
+    };
+
+    let public_values = match GamePublicValues::try_from(&proven.public_values) {
+        Ok(values) => values,
+        Err(error) => return BatchResult::failed(line, Some(summary), error),
+    };
+
+    let proof_file = match proof_dir {
+        Some(dir) => {
+            let path = dir.join(format!("line-{}.bin", line));
+            if let Err(error) = proven.proof.save(&path) {
+                let error = format!("Failed to save proof: {}", error);
+                return BatchResult::failed(line, Some(summary), error);
+            }
+            Some(path)
+        }
+        None => None,
+    };
+
+    BatchResult {
+        line,
+        success: true,
+        input: Some(summary),
+        public_values: Some(public_values),
+        cycles: Some(proven.cycles),
+        timings: Some(proven.timings),
+        digests: Some(proven.digests),
+        proof_file,
+        error: None,
+    }
+}
@@ -0,0 +1,445 @@
+//! Command line tools for memory game proofs.
+//!
+//! ```shell
+//! cargo run --release --bin memory-proof -- execute --input game-input.json
+//! cargo run --release --bin memory-proof -- prove --input game-input.json
+//! cargo run --release --bin memory-proof -- verify memory_game_proof.bin --vkey memory_game_vkey.bin
+//! cargo run --release --bin memory-proof -- vkey
+//! cargo run --release --bin memory-proof -- evm-fixture --input game-input.json --system groth16
+//! cargo run --release --bin memory-proof -- inspect memory_game_proof.bin
+//! cargo run --release --bin memory-proof -- batch --input games.jsonl --parallelism 2
+//! cargo run --release --bin memory-proof -- aggregate --input games.jsonl
+//! ```
+//!
+//! Add `--format json` to get one JSON document on stdout instead of `key: value` lines.
+
+mod aggregate;
+mod batch;
+mod output;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use memory_game_core::hex;
+use memory_proof_lib::decode_public_values;
+use memory_proof_script::{
+    load_proof, load_vkey, proof_digest, prove_game, public_values_digest, save_vkey, verify_proof,
+    GameInput, GameInputSummary, GamePublicValues, ProofDigests, ProofKind, ProofTimings,
+    MEMORY_GAME_ELF,
+};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{utils, EnvProver, HashableKey, ProverClient, SP1Proof, SP1ProofWithPublicValues};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use self::aggregate::AggregateArgs;
+use self::batch::BatchArgs;
+use self::output::Format;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+
+    /// Output format.
+    #[clap(long, value_enum, global = true, default_value = "text")]
+    format: Format,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the program on a game without proving it.
+    Execute(InputArgs),
+    /// Prove a game, verify the proof and save it with its verifying key.
+    Prove(ProveArgs),
+    /// Verify a saved proof and decode its game public values.
+    Verify(VerifyArgs),
+    /// Print (and optionally save) the program verifying key.
+    Vkey(VkeyArgs),
+    /// Prove a game as Groth16 or PLONK and write a Solidity test fixture.
+    EvmFixture(EvmFixtureArgs),
+    /// Show what a saved proof contains, without verifying it.
+    Inspect(InspectArgs),
+    /// Prove every game of a JSONL file and write a JSONL file of results.
+    Batch(BatchArgs),
+    /// Prove a JSONL file of games and aggregate them into one leaderboard proof.
+    Aggregate(AggregateArgs),
+}
+
+impl Command {
+    fn name(&self) -> &'static str {
+        match self {
+            Command::Execute(_) => "execute",
+            Command::Prove(_) => "prove",
+            Command::Verify(_) => "verify",
+            Command::Vkey(_) => "vkey",
+            Command::EvmFixture(_) => "evm-fixture",
+            Command::Inspect(_) => "inspect",
+            Command::Batch(_) => "batch",
+            Command::Aggregate(_) => "aggregate",
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+struct InputArgs {
+    /// Game input file (session seeds, difficulty, card flips and time).
+    #[clap(long)]
+    input: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct ProveArgs {
+    #[clap(flatten)]
+    input: InputArgs,
+
+    /// Where to save the proof.
+    #[clap(long, default_value = "memory_game_proof.bin")]
+    proof: PathBuf,
+
+    /// Where to save the verifying key.
+    #[clap(long, default_value = "memory_game_vkey.bin")]
+    vkey: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// Proof file written by `prove`.
+    #[clap(default_value = "memory_game_proof.bin")]
+    proof: PathBuf,
+
+    /// Verifying key file written by `prove`. Without it the key is rebuilt from the program ELF,
+    /// which only matches proofs of this exact program build.
+    #[clap(long)]
+    vkey: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct VkeyArgs {
+    /// Also save the verifying key to this file.
+    #[clap(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct EvmFixtureArgs {
+    #[clap(flatten)]
+    input: InputArgs,
+
+    #[clap(long, value_enum, default_value = "groth16")]
+    system: ProofSystem,
+
+    /// Directory for `groth16-fixture.json` / `plonk-fixture.json`.
+    #[clap(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../contracts/src/fixtures"))]
+    out_dir: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct InspectArgs {
+    /// Proof file written by `prove` or `evm-fixture`.
+    #[clap(default_value = "memory_game_proof.bin")]
+    proof: PathBuf,
+}
+
+/// Enum representing the available proof systems
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ProofSystem {
+    Plonk,
+    Groth16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecuteReport {
+    input_file: PathBuf,
+    input: GameInputSummary,
+    public_values: GamePublicValues,
+    cycles: u64,
+    execute_ms: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProveReport {
+    input_file: PathBuf,
+    input: GameInputSummary,
+    public_values: GamePublicValues,
+    cycles: u64,
+    timings: ProofTimings,
+    digests: ProofDigests,
+    proof_file: PathBuf,
+    vkey_file: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerifyReport {
+    verified: bool,
+    proof_file: PathBuf,
+    public_values: GamePublicValues,
+    digests: ProofDigests,
+    verify_ms: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VkeyReport {
+    vkey_hash: String,
+    vkey_file: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EvmFixtureReport {
+    input_file: PathBuf,
+    input: GameInputSummary,
+    system: String,
+    public_values: GamePublicValues,
+    prove_ms: u64,
+    fixture_file: PathBuf,
+    vkey_hash: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InspectReport {
+    proof_file: PathBuf,
+    kind: &'static str,
+    sp1_version: String,
+    public_values: GamePublicValues,
+    proof_digest: String,
+    public_values_hash: String,
+}
+
+/// A fixture that can be used to test the verification of SP1 zkVM proofs inside Solidity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SP1MemoryGameProofFixture {
+    moves: u32,
+    time: u32,
+    matched_pairs: u32,
+    score: u32,
+    is_complete: bool,
+    difficulty: String,
+    session_id: String,
+    nonce: String,
+    player: String,
+    issuer: String,
+    vkey: String,
+    public_values: String,
+    proof: String,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let format = cli.format;
+    let command = cli.command.name();
+
+    // Setup logging (in JSON mode stdout only carries the result document)
+    if format == Format::Text {
+        utils::setup_logger();
+    }
+
+    let client = ProverClient::from_env();
+
+    let result = match cli.command {
+        Command::Execute(args) => {
+            execute(&client, args).map(|report| output::print(format, command, &report))
+        }
+        Command::Prove(args) => {
+            prove(&client, args, format).map(|report| output::print(format, command, &report))
+        }
+        Command::Verify(args) => {
+            verify(&client, args).map(|report| output::print(format, command, &report))
+        }
+        Command::Vkey(args) => {
+            vkey(&client, args).map(|report| output::print(format, command, &report))
+        }
+        Command::EvmFixture(args) => {
+            evm_fixture(&client, args).map(|report| output::print(format, command, &report))
+        }
+        Command::Inspect(args) => {
+            inspect(args).map(|report| output::print(format, command, &report))
+        }
+        Command::Batch(args) => {
+            batch::batch(&client, args).map(|report| output::print(format, command, &report))
+        }
+        Command::Aggregate(args) => aggregate::aggregate(&client, args)
+            .map(|report| output::print(format, command, &report)),
+    };
+
+    if let Err(error) = result {
+        output::print_error(format, command, &error);
+        std::process::exit(1);
+    }
+}
+
+fn execute(client: &EnvProver, args: InputArgs) -> Result<ExecuteReport, String> {
+    let input = GameInput::read(&args.input)?;
+    input.check()?;
+
+    let start = Instant::now();
+    let (public_values, report) = client
+        .execute(MEMORY_GAME_ELF, &input.stdin())
+        .run()
+        .map_err(|error| format!("Execution failed: {}", error))?;
+    let execute_ms = start.elapsed().as_millis() as u64;
+
+    Ok(ExecuteReport {
+        input_file: args.input,
+        input: input.summary(),
+        public_values: decode_game_values(public_values.as_slice())?,
+        cycles: report.total_instruction_count(),
+        execute_ms,
+    })
+}
+
+fn prove(client: &EnvProver, args: ProveArgs, format: Format) -> Result<ProveReport, String> {
+    let input = GameInput::read(&args.input.input)?;
+    input.check()?;
+
+    let (pk, vk) = client.setup(MEMORY_GAME_ELF);
+    let proven = prove_game(client, &pk, &vk, &input, ProofKind::Core, |stage| {
+        output::progress(format, &format!("{:?}...", stage));
+    })?;
+
+    proven
+        .proof
+        .save(&args.proof)
+        .map_err(|error| format!("Failed to save proof: {}", error))?;
+    save_vkey(&vk, &args.vkey)?;
+
+    Ok(ProveReport {
+        input_file: args.input.input,
+        input: input.summary(),
+        public_values: GamePublicValues::try_from(&proven.public_values)?,
+        cycles: proven.cycles,
+        timings: proven.timings,
+        digests: proven.digests,
+        proof_file: args.proof,
+        vkey_file: args.vkey,
+    })
+}
+
+fn verify(client: &EnvProver, args: VerifyArgs) -> Result<VerifyReport, String> {
+    let proof = load_proof(&args.proof)?;
+    let vk = match &args.vkey {
+        Some(path) => load_vkey(path)?,
+        None => client.setup(MEMORY_GAME_ELF).1,
+    };
+
+    let verified = verify_proof(client, &vk, &proof)?;
+
+    Ok(VerifyReport {
+        verified: true,
+        proof_file: args.proof,
+        public_values: verified.public_values,
+        digests: verified.digests,
+        verify_ms: verified.verify_ms,
+    })
+}
+
+fn vkey(client: &EnvProver, args: VkeyArgs) -> Result<VkeyReport, String> {
+    let (_, vk) = client.setup(MEMORY_GAME_ELF);
+
+    if let Some(path) = &args.out {
+        save_vkey(&vk, path)?;
+    }
+
+    Ok(VkeyReport {
+        vkey_hash: vk.bytes32(),
+        vkey_file: args.out,
+    })
+}
+
+fn evm_fixture(client: &EnvProver, args: EvmFixtureArgs) -> Result<EvmFixtureReport, String> {
+    let input = GameInput::read(&args.input.input)?;
+    input.check()?;
+
+    let (pk, vk) = client.setup(MEMORY_GAME_ELF);
+    let stdin = input.stdin();
+
+    // Generate the proof based on the selected proof system.
+    let start = Instant::now();
+    let proof = match args.system {
+        ProofSystem::Plonk => client.prove(&pk, &stdin).plonk().run(),
+        ProofSystem::Groth16 => client.prove(&pk, &stdin).groth16().run(),
+    }
+    .map_err(|error| format!("Proving failed: {}", error))?;
+    let prove_ms = start.elapsed().as_millis() as u64;
+
+    let fixture_file = write_fixture(&proof, &vk.bytes32(), args.system, &args.out_dir)?;
+
+    Ok(EvmFixtureReport {
+        input_file: args.input.input,
+        input: input.summary(),
+        system: format!("{:?}", args.system).to_lowercase(),
+        public_values: decode_game_values(proof.public_values.as_slice())?,
+        prove_ms,
+        fixture_file,
+        vkey_hash: vk.bytes32(),
+    })
+}
+
+/// Writes a Solidity test fixture for the proof and returns its path.
+fn write_fixture(
+    proof: &SP1ProofWithPublicValues,
+    vkey: &str,
+    system: ProofSystem,
+    out_dir: &Path,
+) -> Result<PathBuf, String> {
+    // Deserialize the public values.
+    let bytes = proof.public_values.as_slice();
+    let values = decode_game_values(bytes)?;
+
+    // Create the testing fixture so we can test things end-to-end.
+    let fixture = SP1MemoryGameProofFixture {
+        moves: values.moves,
+        time: values.time,
+        matched_pairs: values.matched_pairs,
+        score: values.score,
+        is_complete: values.is_complete,
+        difficulty: values.difficulty,
+        session_id: format!("0x{}", values.session_id),
+        nonce: format!("0x{}", values.nonce),
+        player: values.player,
+        issuer: values.issuer,
+        vkey: vkey.to_string(),
+        public_values: format!("0x{}", hex::encode(bytes)),
+        proof: format!("0x{}", hex::encode(&proof.bytes())),
+    };
+
+    // Save the fixture to a file.
+    std::fs::create_dir_all(out_dir)
+        .map_err(|error| format!("Failed to create {}: {}", out_dir.display(), error))?;
+    let path = out_dir.join(format!("{:?}-fixture.json", system).to_lowercase());
+    std::fs::write(&path, serde_json::to_string_pretty(&fixture).unwrap())
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))?;
+
+    Ok(path)
+}
+
+fn inspect(args: InspectArgs) -> Result<InspectReport, String> {
+    let proof = load_proof(&args.proof)?;
+
+    let kind = match &proof.proof {
+        SP1Proof::Core(_) => "core",
+        SP1Proof::Compressed(_) => "compressed",
+        SP1Proof::Plonk(_) => "plonk",
+        SP1Proof::Groth16(_) => "groth16",
+    };
+
+    Ok(InspectReport {
+        kind,
+        sp1_version: proof.sp1_version.clone(),
+        public_values: decode_game_values(proof.public_values.as_slice())?,
+        proof_digest: proof_digest(&proof)?,
+        public_values_hash: public_values_digest(&proof),
+        proof_file: args.proof,
+    })
+}
+
+fn decode_game_values(bytes: &[u8]) -> Result<GamePublicValues, String> {
+    let values = decode_public_values(bytes)
+        .map_err(|error| format!("Failed to decode public values: {}", error))?;
+    GamePublicValues::try_from(&values)
+}
@@ -0,0 +1,81 @@
+//! The output every subcommand shares: a report printed either as `key: value` lines (nested
+//! objects flattened to dotted keys) or as one JSON document on stdout.
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub enum Format {
+    /// `key: value` lines for people.
+    Text,
+    /// One JSON document for scripts: `{ "success": true, "command": ..., ...report }` or
+    /// `{ "success": false, "command": ..., "error": ... }`.
+    Json,
+}
+
+/// Prints a successful report.
+pub fn print(format: Format, command: &str, report: &impl Serialize) {
+    let report = serde_json::to_value(report).expect("reports are plain JSON values");
+
+    match format {
+        Format::Text => print_lines(&report),
+        Format::Json => {
+            let mut document = json!({ "success": true, "command": command });
+            if let (Value::Object(document), Value::Object(report)) = (&mut document, report) {
+                document.extend(report);
+            }
+            println!("{}", serde_json::to_string_pretty(&document).unwrap());
+        }
+    }
+}
+
+/// Prints why a command failed.
+pub fn print_error(format: Format, command: &str, error: &str) {
+    match format {
+        Format::Text => eprintln!("error: {}", error),
+        Format::Json => {
+            let document = json!({ "success": false, "command": command, "error": error });
+            println!("{}", serde_json::to_string_pretty(&document).unwrap());
+        }
+    }
+}
+
+/// Reports a step of a long-running command on stderr. JSON mode stays quiet, like the SP1 logs.
+pub fn progress(format: Format, message: &str) {
+    if format == Format::Text {
+        eprintln!("{}", message);
+    }
+}
+
+fn print_lines(report: &Value) {
+    let mut lines = Vec::new();
+    flatten("", report, &mut lines);
+
+    let width = lines.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    for (key, value) in lines {
+        println!(
+            "{:width$}  {}",
+            format!("{}:", key),
+            value,
+            width = width + 1
+        );
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, lines: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let key = match prefix {
+                    "" => key.clone(),
+                    _ => format!("{}.{}", prefix, key),
+                };
+                flatten(&key, value, lines);
+            }
+        }
+        Value::Null => {}
+        Value::String(text) => lines.push((prefix.to_string(), text.clone())),
+        value => lines.push((prefix.to_string(), value.to_string())),
+    }
+}
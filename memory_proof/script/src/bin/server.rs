@@ -0,0 +1,69 @@
+//! Proving service for the web game.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin memory_server
+//! ```
+
+use k256::ecdsa::SigningKey;
+use memory_game_core::hex;
+use memory_proof_script::service::{self, AppState, Prover};
+use memory_proof_script::ProofKind;
+use sp1_sdk::utils;
+use std::net::SocketAddr;
+
+#[tokio::main]
+async fn main() {
+    // Setup logging
+    utils::setup_logger();
+
+    let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_string());
+
+    // Each proof takes most of the machine, so only prove a few games at a time
+    let workers = std::env::var("PROVER_WORKERS")
+        .ok()
+        .and_then(|workers| workers.parse().ok())
+        .unwrap_or(1);
+
+    // Groth16 (the default) or PLONK proofs can be checked in the browser. Core proofs need a lot
+    // less memory, but only this service can verify them
+    let kind = match std::env::var("PROOF_KIND") {
+        Ok(name) => ProofKind::from_name(&name).expect("PROOF_KIND must be core, plonk or groth16"),
+        Err(_) => ProofKind::Groth16,
+    };
+    if kind == ProofKind::Core {
+        println!("PROOF_KIND is core: players can't verify these proofs in their browser");
+    }
+
+    // Sessions are signed with this key; its address is what contracts accept as the issuer. A
+    // random key is fine for trying things out, but its proofs stop verifying after a restart.
+    let session_key = match std::env::var("SESSION_KEY") {
+        Ok(key) => hex::decode_array::<32>(&key)
+            .and_then(|key| SigningKey::from_slice(&key).ok())
+            .expect("SESSION_KEY must be a 32-byte secp256k1 private key in hex"),
+        Err(_) => {
+            println!("SESSION_KEY is not set, signing sessions with a random key");
+            SigningKey::random(&mut rand::rngs::OsRng)
+        }
+    };
+
+    // Proving and verifying keys are set up once, not per request
+    println!("Setting up the memory game program...");
+    let state = AppState::new(Prover::from_env(kind), workers, session_key);
+    println!("Sessions are issued by {}", state.session_issuer());
+
+    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port))
+        .await
+        .expect("Failed to bind the server port");
+
+    println!(
+        "SP1 API Server running on http://localhost:{} ({} prover worker(s), {} proofs)",
+        port,
+        workers,
+        kind.name()
+    );
+    println!("Generate real ZK proofs with the \"Prove (SP1)\" button in the web interface!");
+
+    // Reveals are limited per client address
+    let app = service::router(state).into_make_service_with_connect_info::<SocketAddr>();
+    axum::serve(listener, app).await.expect("Server error");
+}
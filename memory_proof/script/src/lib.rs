@@ -0,0 +1,470 @@
+//! Pieces shared by the memory game proof binaries: the program ELF, the game input file and the
+//! prove-and-verify pipeline.
+
+pub mod service;
+
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::Game;
+use memory_game_core::{commitment, deck, hex, scoring};
+use memory_proof_lib::{
+    decode_public_values, recover_signer, GameTranscript, MemoryGamePublicValues, SessionTicket,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{
+    include_elf, EnvProver, HashableKey, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin,
+    SP1VerifyingKey,
+};
+use std::path::Path;
+use std::time::Instant;
+
+/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
+pub const MEMORY_GAME_ELF: &[u8] = include_elf!("memory-proof-program");
+
+/// The ELF of the program that verifies many game proofs and commits one leaderboard.
+pub const AGGREGATION_ELF: &[u8] = include_elf!("memory-proof-aggregation");
+
+/// Longest transcript we agree to prove. A full Expert game needs 36 flips; this leaves plenty of
+/// room for misses while keeping a single proof's cost bounded.
+pub const MAX_FLIPS: usize = 2_000;
+
+/// Game transcript sent by the web game, with the session seeds held by the backend.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GameInputFile {
+    commitment: String,
+    nonce: String,
+    server_seed: String,
+    client_seed: String,
+    #[serde(default = "default_difficulty")]
+    difficulty: String,
+    flips: Vec<u32>,
+    time: u32,
+    player: String,
+    signature: String,
+    session_signature: String,
+}
+
+// Games recorded before difficulty levels existed were all Normal
+fn default_difficulty() -> String {
+    Difficulty::default().name().to_string()
+}
+
+/// A parsed game input file: everything the memory game program reads.
+#[derive(Debug, Clone)]
+pub struct GameInput {
+    pub commitment: [u8; 32],
+    /// Issued with the session; a proof of it is accepted only once.
+    pub nonce: [u8; 32],
+    pub server_seed: [u8; 32],
+    pub client_seed: u64,
+    pub difficulty: Difficulty,
+    pub flips: Vec<u32>,
+    pub time: u32,
+    /// Ethereum address of the player claiming the game.
+    pub player: [u8; 20],
+    /// The player's `personal_sign` signature over [`GameInput::transcript`]'s hash.
+    pub signature: [u8; 65],
+    /// The backend's `personal_sign` signature over [`GameInput::ticket`]'s hash, made when it
+    /// opened the session.
+    pub session_signature: [u8; 65],
+}
+
+impl GameInput {
+    /// Parses a game input from its JSON form.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: GameInputFile = serde_json::from_str(json)
+            .map_err(|error| format!("Failed to parse game input: {}", error))?;
+
+        Ok(Self {
+            commitment: hex::decode_array::<32>(&file.commitment)
+                .ok_or("Invalid session commitment")?,
+            nonce: hex::decode_array::<32>(&file.nonce).ok_or("Invalid session nonce")?,
+            server_seed: hex::decode_array::<32>(&file.server_seed).ok_or("Invalid server seed")?,
+            client_seed: deck::parse_seed(&file.client_seed).ok_or("Invalid client seed")?,
+            difficulty: Difficulty::from_name(&file.difficulty).ok_or("Invalid difficulty")?,
+            flips: file.flips,
+            time: file.time,
+            player: hex::decode_array::<20>(&file.player).ok_or("Invalid player address")?,
+            signature: hex::decode_array::<65>(&file.signature)
+                .ok_or("Invalid player signature")?,
+            session_signature: hex::decode_array::<65>(&file.session_signature)
+                .ok_or("Invalid session signature")?,
+        })
+    }
+
+    /// Reads and parses a game input file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+        Self::from_json(&json)
+    }
+
+    /// Replays the transcript natively, so a game the program would reject fails here instead of
+    /// after minutes of proving.
+    pub fn check(&self) -> Result<(), String> {
+        if self.flips.len() > MAX_FLIPS {
+            return Err(format!(
+                "Too many flips: {} (at most {})",
+                self.flips.len(),
+                MAX_FLIPS
+            ));
+        }
+        if !commitment::verify_reveal(&self.commitment, &self.server_seed, self.difficulty) {
+            return Err(
+                "Server seed or difficulty does not match the session commitment".to_string(),
+            );
+        }
+
+        if self.issuer().is_none() {
+            return Err("Session is not signed by a backend".to_string());
+        }
+
+        if recover_signer(&self.transcript().hash(), &self.signature) != Some(self.player) {
+            return Err("Transcript is not signed by the player".to_string());
+        }
+
+        let seed = commitment::deck_seed(&self.server_seed, self.client_seed);
+        let cards = deck::shuffled_deck(seed, self.difficulty.pairs());
+        let game = Game::replay(self.difficulty, cards, &self.flips)
+            .map_err(|rejection| format!("Invalid transcript: {:?}", rejection))?;
+
+        if !scoring::is_possible_time(self.difficulty, self.time, game.is_complete()) {
+            let reason = if game.is_complete() {
+                "a won game must end before"
+            } else {
+                "over"
+            };
+            return Err(format!(
+                "Time {} is {} the {} second limit",
+                self.time,
+                reason,
+                self.difficulty.time_limit()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The game as the player signs it.
+    pub fn transcript(&self) -> GameTranscript {
+        GameTranscript {
+            sessionId: self.commitment.into(),
+            nonce: self.nonce.into(),
+            clientSeed: self.client_seed,
+            difficulty: self.difficulty.id(),
+            flips: self.flips.clone(),
+            time: self.time,
+        }
+    }
+
+    /// The session as the backend signs it.
+    pub fn ticket(&self) -> SessionTicket {
+        SessionTicket {
+            sessionId: self.commitment.into(),
+            nonce: self.nonce.into(),
+            difficulty: self.difficulty.id(),
+        }
+    }
+
+    /// Address of the backend that signed the session, which the program commits as `issuer`.
+    pub fn issuer(&self) -> Option<[u8; 20]> {
+        recover_signer(&self.ticket().hash(), &self.session_signature)
+    }
+
+    /// What a report shows about the input (the server seed stays out of it).
+    pub fn summary(&self) -> GameInputSummary {
+        GameInputSummary {
+            session: hex::encode(&self.commitment),
+            nonce: hex::encode(&self.nonce),
+            client_seed: deck::format_seed(self.client_seed),
+            difficulty: self.difficulty.name().to_string(),
+            flips: self.flips.len(),
+            time: self.time,
+            player: format!("0x{}", hex::encode(&self.player)),
+            issuer: self
+                .issuer()
+                .map(|issuer| format!("0x{}", hex::encode(&issuer))),
+        }
+    }
+
+    /// The SP1 input stream, in the order the program reads it.
+    pub fn stdin(&self) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&self.commitment);
+        stdin.write(&self.nonce);
+        stdin.write(&self.server_seed);
+        stdin.write(&self.client_seed);
+        stdin.write(&self.difficulty.id());
+        stdin.write(&self.flips);
+        stdin.write(&self.time);
+        stdin.write(&self.player);
+        stdin.write(&self.signature.to_vec());
+        stdin.write(&self.session_signature.to_vec());
+        stdin
+    }
+}
+
+/// A game input as shown in reports.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameInputSummary {
+    pub session: String,
+    pub nonce: String,
+    pub client_seed: String,
+    pub difficulty: String,
+    pub flips: usize,
+    pub time: u32,
+    pub player: String,
+    /// `None` when the session signature is invalid.
+    pub issuer: Option<String>,
+}
+
+/// Public values of a memory game proof in a JSON-friendly form.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamePublicValues {
+    pub moves: u32,
+    pub time: u32,
+    pub matched_pairs: u32,
+    pub score: u32,
+    pub is_complete: bool,
+    pub difficulty: String,
+    pub session_id: String,
+    pub nonce: String,
+    pub player: String,
+    /// The backend that dealt the session.
+    pub issuer: String,
+}
+
+impl TryFrom<&MemoryGamePublicValues> for GamePublicValues {
+    type Error = String;
+
+    fn try_from(values: &MemoryGamePublicValues) -> Result<Self, String> {
+        let difficulty = values
+            .difficulty_preset()
+            .ok_or_else(|| format!("Unknown difficulty id: {}", values.difficulty))?;
+
+        Ok(Self {
+            moves: values.moves,
+            time: values.time,
+            matched_pairs: values.matchedPairs,
+            score: values.score,
+            is_complete: values.isComplete,
+            difficulty: difficulty.name().to_string(),
+            session_id: hex::encode(values.sessionId.as_slice()),
+            nonce: hex::encode(values.nonce.as_slice()),
+            player: format!("0x{}", hex::encode(values.player.as_slice())),
+            issuer: format!("0x{}", hex::encode(values.issuer.as_slice())),
+        })
+    }
+}
+
+/// The kind of proof [`prove_game`] makes. Only PLONK and Groth16 proofs can be verified on-chain
+/// or in the browser, and they need far more memory to make.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofKind {
+    #[default]
+    Core,
+    Plonk,
+    Groth16,
+}
+
+impl ProofKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ProofKind::Core => "core",
+            ProofKind::Plonk => "plonk",
+            ProofKind::Groth16 => "groth16",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [ProofKind::Core, ProofKind::Plonk, ProofKind::Groth16]
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+}
+
+/// A verified proof of a game with its decoded public values.
+pub struct ProvenGame {
+    pub proof: SP1ProofWithPublicValues,
+    pub kind: ProofKind,
+    /// Instructions executed by the program.
+    pub cycles: u64,
+    pub public_values: MemoryGamePublicValues,
+    pub digests: ProofDigests,
+    pub timings: ProofTimings,
+}
+
+/// How long each step of [`prove_game`] took, in milliseconds.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofTimings {
+    pub execute_ms: u64,
+    pub prove_ms: u64,
+    pub verify_ms: u64,
+}
+
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
+}
+
+/// The steps of [`prove_game`], in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofStage {
+    Executing,
+    Proving,
+    Verifying,
+}
+
+/// Executes the program on `input`, then proves the game as a `kind` proof and verifies it.
+/// `on_stage` is called as each step starts.
+pub fn prove_game(
+    client: &EnvProver,
+    pk: &SP1ProvingKey,
+    vk: &SP1VerifyingKey,
+    input: &GameInput,
+    kind: ProofKind,
+    mut on_stage: impl FnMut(ProofStage),
+) -> Result<ProvenGame, String> {
+    let stdin = input.stdin();
+
+    // Executing is cheap, so a transcript the program rejects fails before proving starts
+    on_stage(ProofStage::Executing);
+    let start = Instant::now();
+    let (_, report) = client
+        .execute(MEMORY_GAME_ELF, &stdin)
+        .run()
+        .map_err(|error| format!("Execution failed: {}", error))?;
+    let execute_ms = elapsed_ms(start);
+
+    on_stage(ProofStage::Proving);
+    let start = Instant::now();
+    let proof = match kind {
+        ProofKind::Core => client.prove(pk, &stdin).core().run(),
+        ProofKind::Plonk => client.prove(pk, &stdin).plonk().run(),
+        ProofKind::Groth16 => client.prove(pk, &stdin).groth16().run(),
+    }
+    .map_err(|error| format!("Proving failed: {}", error))?;
+    let prove_ms = elapsed_ms(start);
+
+    on_stage(ProofStage::Verifying);
+    let start = Instant::now();
+    client
+        .verify(&proof, vk)
+        .map_err(|error| format!("Verification failed: {}", error))?;
+    let verify_ms = elapsed_ms(start);
+
+    let public_values = decode_public_values(proof.public_values.as_slice())
+        .map_err(|error| format!("Failed to decode public values: {}", error))?;
+    let digests = ProofDigests::new(&proof, vk)?;
+
+    Ok(ProvenGame {
+        proof,
+        kind,
+        cycles: report.total_instruction_count(),
+        public_values,
+        digests,
+        timings: ProofTimings {
+            execute_ms,
+            prove_ms,
+            verify_ms,
+        },
+    })
+}
+
+/// Identifiers of a proof that anyone holding the proof file can recompute.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofDigests {
+    /// SHA-256 of the serialized proof, i.e. of the file written by `SP1ProofWithPublicValues::save`.
+    pub proof: String,
+    /// The program verification key hash (`programVKey` in Solidity).
+    pub vkey: String,
+    /// SHA-256 of the ABI-encoded public values.
+    pub public_values: String,
+}
+
+impl ProofDigests {
+    pub fn new(proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> Result<Self, String> {
+        Ok(Self {
+            proof: proof_digest(proof)?,
+            vkey: vk.bytes32(),
+            public_values: public_values_digest(proof),
+        })
+    }
+}
+
+/// SHA-256 of the serialized proof, as a `0x` hex string.
+pub fn proof_digest(proof: &SP1ProofWithPublicValues) -> Result<String, String> {
+    let bytes = bincode::serialize(proof)
+        .map_err(|error| format!("Failed to serialize proof: {}", error))?;
+    Ok(format!("0x{}", hex::encode(&Sha256::digest(&bytes))))
+}
+
+/// SHA-256 of the proof's public values, as a `0x` hex string.
+pub fn public_values_digest(proof: &SP1ProofWithPublicValues) -> String {
+    format!(
+        "0x{}",
+        hex::encode(&Sha256::digest(proof.public_values.as_slice()))
+    )
+}
+
+/// A saved proof that passed verification.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedProof {
+    pub public_values: GamePublicValues,
+    pub digests: ProofDigests,
+    pub verify_ms: u64,
+}
+
+/// Verifies a proof against `vk` and decodes its game public values.
+pub fn verify_proof(
+    client: &EnvProver,
+    vk: &SP1VerifyingKey,
+    proof: &SP1ProofWithPublicValues,
+) -> Result<VerifiedProof, String> {
+    let start = Instant::now();
+    client
+        .verify(proof, vk)
+        .map_err(|error| format!("Verification failed: {}", error))?;
+    let verify_ms = elapsed_ms(start);
+
+    let values = decode_public_values(proof.public_values.as_slice())
+        .map_err(|error| format!("Failed to decode public values: {}", error))?;
+
+    Ok(VerifiedProof {
+        public_values: GamePublicValues::try_from(&values)?,
+        digests: ProofDigests::new(proof, vk)?,
+        verify_ms,
+    })
+}
+
+/// Loads a proof written by `SP1ProofWithPublicValues::save`.
+pub fn load_proof(path: impl AsRef<Path>) -> Result<SP1ProofWithPublicValues, String> {
+    let path = path.as_ref();
+    SP1ProofWithPublicValues::load(path)
+        .map_err(|error| format!("Failed to load proof {}: {}", path.display(), error))
+}
+
+/// Saves a verifying key, so proofs can be checked later without the program ELF.
+pub fn save_vkey(vk: &SP1VerifyingKey, path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+    let bytes =
+        bincode::serialize(vk).map_err(|error| format!("Failed to serialize vkey: {}", error))?;
+    std::fs::write(path, bytes)
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+}
+
+/// Loads a verifying key written by [`save_vkey`].
+pub fn load_vkey(path: impl AsRef<Path>) -> Result<SP1VerifyingKey, String> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    bincode::deserialize(&bytes)
+        .map_err(|error| format!("Failed to load vkey {}: {}", path.display(), error))
+}
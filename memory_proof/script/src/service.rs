@@ -0,0 +1,585 @@
+//! HTTP proving service for the web game (`sp1-bridge.js`).
+//!
+//! Holds the commit-reveal deck sessions and proves game transcripts with the SP1 prover linked in
+//! directly, so request data never ends up in a shell command. Proofs run as background
+//! [`jobs`] that the web game polls.
+
+pub mod jobs;
+
+use crate::{GameInput, GamePublicValues, ProofKind, ProvenGame, MEMORY_GAME_ELF};
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use k256::ecdsa::SigningKey;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::{commitment, deck, hex, scoring};
+use memory_proof_lib::{address_of, sign_hash, SessionTicket};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sp1_sdk::{EnvProver, ProverClient, SP1ProvingKey, SP1VerifyingKey};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tower_http::cors::CorsLayer;
+
+use self::jobs::{JobView, Jobs};
+
+/// Open sessions before new ones are turned away. Anyone can open one, so this bounds the memory
+/// they take.
+const MAX_SESSIONS: usize = 10_000;
+
+/// How long a session stays around: long enough to play the slowest game and ask for its proof.
+const SESSION_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Decks one client may see in a [`SESSION_TTL`]. The server seed has to be revealed before the
+/// game is played, since the deck is dealt from it, so a client could open and reveal sessions
+/// until it gets an easy deck. This bounds how many it can look at. Clients are told apart by IP
+/// address, so behind a proxy they share one budget.
+const REVEALS_PER_CLIENT: usize = 30;
+
+/// A commit-reveal deck session. The client seed can only be chosen once, and the nonce can only
+/// be proved once. The difficulty is committed with the server seed, and the session key signs the
+/// session so proofs can only be made of decks this service dealt.
+///
+/// A nonce is only refused again for as long as its session lives: once the session expires its
+/// game can't be proved here at all. Anything that accepts proofs for good, like the leaderboard
+/// contract, keeps its own record of used nonces.
+struct Session {
+    server_seed: [u8; 32],
+    difficulty: Difficulty,
+    commitment: [u8; 32],
+    nonce: [u8; 32],
+    signature: [u8; 65],
+    client_seed: Option<u64>,
+    // A proof of the session's game is done or in progress (a failed proof clears it)
+    proved: bool,
+    created: Instant,
+}
+
+type Sessions = Mutex<HashMap<String, Session>>;
+
+/// The prover client with the program's keys, set up once at startup.
+pub struct Prover {
+    client: EnvProver,
+    pk: SP1ProvingKey,
+    vk: SP1VerifyingKey,
+    kind: ProofKind,
+}
+
+impl Prover {
+    /// A prover making `kind` proofs.
+    pub fn from_env(kind: ProofKind) -> Self {
+        let client = ProverClient::from_env();
+        let (pk, vk) = client.setup(MEMORY_GAME_ELF);
+        Self {
+            client,
+            pk,
+            vk,
+            kind,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AppState {
+    sessions: Arc<Sessions>,
+    // When each client was last shown a deck, within the last SESSION_TTL
+    reveals: Arc<Mutex<HashMap<IpAddr, Vec<Instant>>>>,
+    prover: Arc<Prover>,
+    jobs: Arc<Jobs>,
+    session_key: Arc<SigningKey>,
+}
+
+impl AppState {
+    /// Service state proving at most `workers` games at the same time and signing its sessions
+    /// with `session_key`.
+    pub fn new(prover: Prover, workers: usize, session_key: SigningKey) -> Self {
+        Self {
+            sessions: Arc::default(),
+            reveals: Arc::default(),
+            prover: Arc::new(prover),
+            jobs: Arc::new(Jobs::new(workers)),
+            session_key: Arc::new(session_key),
+        }
+    }
+
+    /// Address of the session key: the `issuer` committed by every proof of this service's games.
+    pub fn session_issuer(&self) -> String {
+        format!(
+            "0x{}",
+            hex::encode(&address_of(self.session_key.verifying_key()))
+        )
+    }
+}
+
+/// Routes of the proving service. Serve them with
+/// `into_make_service_with_connect_info::<SocketAddr>`: reveals are limited per client address.
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/api/session", post(open_session))
+        .route("/api/session/{id}/reveal", post(reveal_session))
+        .route("/api/transcript-hash", post(transcript_hash))
+        .route("/api/generate-proof", post(generate_proof))
+        .route("/api/jobs/{id}", get(job_status))
+        .route("/health", get(health))
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+/// An error response: `{ "success": false, "error": ... }` with a status code.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(rejection.status(), rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "success": false, "error": self.message }));
+        (self.status, body).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionRequest {
+    difficulty: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionResponse {
+    session_id: String,
+    commitment: String,
+    nonce: String,
+    session_signature: String,
+}
+
+// Start a session: commit to a secret server seed and the difficulty before the player picks
+// their seed, and sign the session as ours
+async fn open_session(
+    State(state): State<AppState>,
+    request: Result<Json<SessionRequest>, JsonRejection>,
+) -> Result<Json<SessionResponse>, ApiError> {
+    let Json(request) = request?;
+    let difficulty = parse_difficulty(request.difficulty.as_deref())?;
+
+    let mut rng = rand::thread_rng();
+    let mut id = [0u8; 16];
+    let mut server_seed = [0u8; 32];
+    let mut nonce = [0u8; 32];
+    rng.fill_bytes(&mut id);
+    rng.fill_bytes(&mut server_seed);
+    rng.fill_bytes(&mut nonce);
+
+    let session_id = hex::encode(&id);
+    let commitment = commitment::commit(&server_seed, difficulty);
+    let ticket = SessionTicket {
+        sessionId: commitment.into(),
+        nonce: nonce.into(),
+        difficulty: difficulty.id(),
+    };
+    let signature = sign_hash(&ticket.hash(), &state.session_key);
+
+    let mut sessions = state.sessions.lock().unwrap();
+    sessions.retain(|_, session| session.created.elapsed() < SESSION_TTL);
+    if sessions.len() >= MAX_SESSIONS {
+        return Err(ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many open sessions, try again later",
+        ));
+    }
+
+    sessions.insert(
+        session_id.clone(),
+        Session {
+            server_seed,
+            difficulty,
+            commitment,
+            nonce,
+            signature,
+            client_seed: None,
+            proved: false,
+            created: Instant::now(),
+        },
+    );
+
+    Ok(Json(SessionResponse {
+        session_id,
+        commitment: hex::encode(&commitment),
+        nonce: hex::encode(&nonce),
+        session_signature: format!("0x{}", hex::encode(&signature)),
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RevealRequest {
+    client_seed: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RevealResponse {
+    server_seed: String,
+}
+
+// Record the player's seed, then reveal the server seed
+async fn reveal_session(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    Path(id): Path<String>,
+    request: Result<Json<RevealRequest>, JsonRejection>,
+) -> Result<Json<RevealResponse>, ApiError> {
+    let Json(request) = request?;
+
+    let client_seed = Some(&request.client_seed)
+        .filter(|seed| seed.len() == 16)
+        .and_then(|seed| deck::parse_seed(seed))
+        .ok_or_else(|| ApiError::bad_request("clientSeed must be 16 hex digits"))?;
+
+    let mut sessions = state.sessions.lock().unwrap();
+    let session = sessions
+        .get_mut(&id)
+        .filter(|session| session.created.elapsed() < SESSION_TTL)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "Unknown session"))?;
+
+    if session.client_seed.is_some_and(|seed| seed != client_seed) {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "Session already has a client seed",
+        ));
+    }
+
+    // Revealing the same session again shows no new deck
+    if session.client_seed.is_none() {
+        let mut reveals = state.reveals.lock().unwrap();
+        reveals.retain(|_, times| {
+            times.retain(|time| time.elapsed() < SESSION_TTL);
+            !times.is_empty()
+        });
+        let times = reveals.entry(client.ip()).or_default();
+        if times.len() >= REVEALS_PER_CLIENT {
+            return Err(ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many decks dealt to this client, try again later",
+            ));
+        }
+        times.push(Instant::now());
+    }
+
+    session.client_seed = Some(client_seed);
+
+    Ok(Json(RevealResponse {
+        server_seed: hex::encode(&session.server_seed),
+    }))
+}
+
+/// A finished game as the web game sends it. Score, moves and matched pairs are ignored: the proof
+/// derives them from the transcript.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GameRequest {
+    session_id: Option<String>,
+    nonce: Option<String>,
+    difficulty: Option<String>,
+    flips: Vec<u32>,
+    time: u32,
+}
+
+/// A game with the player's claim on it: their address and `personal_sign` signature over the
+/// transcript hash.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofRequest {
+    #[serde(flatten)]
+    game: GameRequest,
+    player: Option<String>,
+    signature: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptHashResponse {
+    transcript_hash: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProvenGameData {
+    moves: u32,
+    time: u32,
+    matched_pairs: u32,
+    difficulty: String,
+    player: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofDetails {
+    algorithm: &'static str,
+    verification_method: &'static str,
+    score_formula: &'static str,
+    digest_algorithm: &'static str,
+    created_at: String,
+}
+
+/// The result of a finished proof job.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofResponse {
+    success: bool,
+    proof_hash: String,
+    vkey_hash: String,
+    public_values_hash: String,
+    /// `core`, `plonk` or `groth16`.
+    proof_kind: ProofKind,
+    /// The proof as verifiers take it (`0x` hex). Only PLONK and Groth16 proofs have one, and the
+    /// web game checks them with `sp1-verifier` in the browser.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_bytes: Option<String>,
+    calculated_score: u32,
+    is_real_proof: bool,
+    game_data: ProvenGameData,
+    remaining_time: u32,
+    session_commitment: String,
+    public_values: GamePublicValues,
+    public_values_bytes: String,
+    proof_details: ProofDetails,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JobSubmitted {
+    success: bool,
+    job_id: String,
+    status: jobs::JobStatus,
+}
+
+// The hash the player signs to claim a game, before asking for its proof
+async fn transcript_hash(
+    State(state): State<AppState>,
+    request: Result<Json<GameRequest>, JsonRejection>,
+) -> Result<Json<TranscriptHashResponse>, ApiError> {
+    let Json(request) = request?;
+
+    // The signature is checked with the proof request
+    let input = game_input(&state, &request, [0; 20], [0; 65])?;
+
+    Ok(Json(TranscriptHashResponse {
+        transcript_hash: format!("0x{}", hex::encode(&input.transcript().hash())),
+    }))
+}
+
+// Queue a proof of a game transcript from its deck session
+async fn generate_proof(
+    State(state): State<AppState>,
+    request: Result<Json<ProofRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<JobSubmitted>), ApiError> {
+    let Json(request) = request?;
+
+    let player = request
+        .player
+        .as_deref()
+        .and_then(hex::decode_array::<20>)
+        .ok_or_else(|| ApiError::bad_request("player must be an Ethereum address"))?;
+    let signature = request
+        .signature
+        .as_deref()
+        .and_then(hex::decode_array::<65>)
+        .ok_or_else(|| ApiError::bad_request("signature must be 65 bytes of hex"))?;
+
+    let input = game_input(&state, &request.game, player, signature)?;
+    input.check().map_err(ApiError::bad_request)?;
+
+    // Each game is proved once: a second request for the same nonce would only replay it
+    let session_id = request.game.session_id.unwrap_or_default();
+    {
+        let mut sessions = state.sessions.lock().unwrap();
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "Unknown session"))?;
+        if session.proved {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "This game's nonce was already used for a proof",
+            ));
+        }
+        session.proved = true;
+    }
+
+    // A proof that fails doesn't use up the game
+    let sessions = state.sessions.clone();
+    let released_id = session_id.clone();
+    let release = move || release_session(&sessions, &released_id);
+
+    let job_id = state
+        .jobs
+        .submit(state.prover.clone(), input, release)
+        .inspect_err(|_| {
+            // Nothing was queued, so the game can still be proved
+            release_session(&state.sessions, &session_id);
+        })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(JobSubmitted {
+            success: true,
+            job_id,
+            status: jobs::JobStatus::Queued,
+        }),
+    ))
+}
+
+// Status of a proof job (and its result once done)
+async fn job_status(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<JobView>, ApiError> {
+    state
+        .jobs
+        .view(&id)
+        .map(Json)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "Unknown job"))
+}
+
+/// The response for a verified proof, built from its decoded public values.
+fn proof_response(proven: &ProvenGame) -> Result<ProofResponse, String> {
+    let values = &proven.public_values;
+    let public_values = GamePublicValues::try_from(values)?;
+    let difficulty = values.difficulty_preset().unwrap_or_default();
+
+    Ok(ProofResponse {
+        success: true,
+        proof_hash: proven.digests.proof.clone(),
+        vkey_hash: proven.digests.vkey.clone(),
+        public_values_hash: proven.digests.public_values.clone(),
+        proof_kind: proven.kind,
+        proof_bytes: match proven.kind {
+            ProofKind::Core => None,
+            ProofKind::Plonk | ProofKind::Groth16 => {
+                Some(format!("0x{}", hex::encode(&proven.proof.bytes())))
+            }
+        },
+        calculated_score: values.score,
+        is_real_proof: true,
+        game_data: ProvenGameData {
+            moves: values.moves,
+            time: values.time,
+            matched_pairs: values.matchedPairs,
+            difficulty: difficulty.name().to_string(),
+            player: public_values.player.clone(),
+        },
+        remaining_time: scoring::remaining_time(difficulty, values.time),
+        session_commitment: public_values.session_id.clone(),
+        public_values,
+        public_values_bytes: format!("0x{}", hex::encode(proven.proof.public_values.as_slice())),
+        proof_details: ProofDetails {
+            algorithm: "SP1 ZK-STARK",
+            verification_method: "Real SP1 RISC-V zkVM",
+            score_formula: "Remaining Time - Moves",
+            digest_algorithm: "SHA-256",
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        },
+    })
+}
+
+// The deck can only be rebuilt from a session whose seeds we know
+fn game_input(
+    state: &AppState,
+    request: &GameRequest,
+    player: [u8; 20],
+    signature: [u8; 65],
+) -> Result<GameInput, ApiError> {
+    let session_id = request
+        .session_id
+        .as_deref()
+        .ok_or_else(|| ApiError::bad_request("No deck session for this game"))?;
+
+    let sessions = state.sessions.lock().unwrap();
+    let session = sessions
+        .get(session_id)
+        .filter(|session| session.created.elapsed() < SESSION_TTL)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "Unknown session"))?;
+    let client_seed = session
+        .client_seed
+        .ok_or_else(|| ApiError::bad_request("Session seed was never revealed"))?;
+
+    // The client carries the nonce it was issued; it must be this session's
+    if request.nonce.as_deref().and_then(hex::decode_array::<32>) != Some(session.nonce) {
+        return Err(ApiError::bad_request(
+            "Nonce does not belong to this session",
+        ));
+    }
+
+    // The difficulty was committed with the session; the client may only repeat it
+    if request.difficulty.is_some()
+        && parse_difficulty(request.difficulty.as_deref())? != session.difficulty
+    {
+        return Err(ApiError::bad_request(
+            "Difficulty does not match the session",
+        ));
+    }
+
+    Ok(GameInput {
+        commitment: session.commitment,
+        nonce: session.nonce,
+        server_seed: session.server_seed,
+        client_seed,
+        difficulty: session.difficulty,
+        flips: request.flips.clone(),
+        time: request.time,
+        player,
+        signature,
+        session_signature: session.signature,
+    })
+}
+
+// Let a session's game be proved again, if the session is still around
+fn release_session(sessions: &Sessions, id: &str) {
+    if let Some(session) = sessions.lock().unwrap().get_mut(id) {
+        session.proved = false;
+    }
+}
+
+// A difficulty name from a request (Normal when missing, like games recorded before difficulties)
+fn parse_difficulty(name: Option<&str>) -> Result<Difficulty, ApiError> {
+    match name {
+        Some(name) => Difficulty::from_name(name)
+            .ok_or_else(|| ApiError::bad_request(format!("Unknown difficulty: {}", name))),
+        None => Ok(Difficulty::default()),
+    }
+}
+
+// Health check endpoint, with the session issuer a leaderboard contract should pin
+async fn health(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(json!({
+        "status": "ok",
+        "sessionIssuer": state.session_issuer(),
+        "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+    }))
+}
@@ -0,0 +1,233 @@
+//! Proof jobs: requests are queued and proved in the background by a bounded pool of workers, and
+//! the web game polls their status.
+
+use super::{proof_response, ApiError, ProofResponse, Prover};
+use crate::{prove_game, GameInput, ProofStage};
+use axum::http::StatusCode;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Jobs waiting for a worker before new ones are turned away.
+const MAX_QUEUED_JOBS: usize = 32;
+
+/// How long finished jobs stay around for polling.
+const FINISHED_JOB_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Executing,
+    Proving,
+    Verifying,
+    Done,
+    Failed,
+}
+
+impl From<ProofStage> for JobStatus {
+    fn from(stage: ProofStage) -> Self {
+        match stage {
+            ProofStage::Executing => JobStatus::Executing,
+            ProofStage::Proving => JobStatus::Proving,
+            ProofStage::Verifying => JobStatus::Verifying,
+        }
+    }
+}
+
+struct Job {
+    // When each status was entered, in order (starting with Queued)
+    stages: Vec<(JobStatus, Instant)>,
+    result: Option<ProofResponse>,
+    error: Option<String>,
+}
+
+impl Job {
+    fn status(&self) -> JobStatus {
+        self.stages
+            .last()
+            .map(|(status, _)| *status)
+            .unwrap_or(JobStatus::Queued)
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.status(), JobStatus::Done | JobStatus::Failed)
+    }
+
+    // Milliseconds spent in `status` (up to now if it is the current one)
+    fn time_in(&self, status: JobStatus) -> Option<u64> {
+        let index = self.stages.iter().position(|(stage, _)| *stage == status)?;
+        let start = self.stages[index].1;
+        let end = match self.stages.get(index + 1) {
+            Some((_, next)) => *next,
+            None if self.is_finished() => start,
+            None => Instant::now(),
+        };
+        Some(end.duration_since(start).as_millis() as u64)
+    }
+
+    fn total_ms(&self) -> u64 {
+        let start = self.stages[0].1;
+        let end = if self.is_finished() {
+            self.stages.last().map(|(_, at)| *at).unwrap_or(start)
+        } else {
+            Instant::now()
+        };
+        end.duration_since(start).as_millis() as u64
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobTimings {
+    queued_ms: Option<u64>,
+    executing_ms: Option<u64>,
+    proving_ms: Option<u64>,
+    verifying_ms: Option<u64>,
+    total_ms: u64,
+}
+
+/// What `GET /api/jobs/{id}` returns.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobView {
+    job_id: String,
+    status: JobStatus,
+    timings: JobTimings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<ProofResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Proof jobs and the workers that run them.
+pub struct Jobs {
+    jobs: Mutex<HashMap<String, Job>>,
+    workers: Arc<Semaphore>,
+}
+
+impl Jobs {
+    /// A queue that proves at most `workers` games at the same time.
+    pub fn new(workers: usize) -> Self {
+        Self {
+            jobs: Mutex::default(),
+            workers: Arc::new(Semaphore::new(workers.max(1))),
+        }
+    }
+
+    /// Queues a proof of `input` and returns the job id. `on_failed` runs if the proof fails, so
+    /// the game can be sent again.
+    pub fn submit(
+        self: &Arc<Self>,
+        prover: Arc<Prover>,
+        input: GameInput,
+        on_failed: impl FnOnce() + Send + 'static,
+    ) -> Result<String, ApiError> {
+        let mut id = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut id);
+        let id = memory_game_core::hex::encode(&id);
+
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            jobs.retain(|_, job| {
+                !job.is_finished() || job.stages.last().unwrap().1.elapsed() < FINISHED_JOB_TTL
+            });
+
+            let queued = jobs
+                .values()
+                .filter(|job| job.status() == JobStatus::Queued)
+                .count();
+            if queued >= MAX_QUEUED_JOBS {
+                return Err(ApiError::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Proof queue is full, try again later",
+                ));
+            }
+
+            jobs.insert(
+                id.clone(),
+                Job {
+                    stages: vec![(JobStatus::Queued, Instant::now())],
+                    result: None,
+                    error: None,
+                },
+            );
+        }
+
+        let jobs = self.clone();
+        let job_id = id.clone();
+        tokio::spawn(async move {
+            // Wait for a free worker
+            let _permit = jobs.workers.clone().acquire_owned().await.unwrap();
+
+            // Proving blocks for minutes, keep it off the async runtime
+            let progress = jobs.clone();
+            let progress_id = job_id.clone();
+            let outcome = tokio::task::spawn_blocking(move || {
+                let on_stage = |stage: ProofStage| progress.enter(&progress_id, stage.into());
+                prove_game(
+                    &prover.client,
+                    &prover.pk,
+                    &prover.vk,
+                    &input,
+                    prover.kind,
+                    on_stage,
+                )
+                .and_then(|proven| proof_response(&proven))
+            })
+            .await
+            .unwrap_or_else(|error| Err(error.to_string()));
+
+            if outcome.is_err() {
+                on_failed();
+            }
+            jobs.finish(&job_id, outcome);
+        });
+
+        Ok(id)
+    }
+
+    /// The current state of a job.
+    pub fn view(&self, id: &str) -> Option<JobView> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs.get(id)?;
+
+        Some(JobView {
+            job_id: id.to_string(),
+            status: job.status(),
+            timings: JobTimings {
+                queued_ms: job.time_in(JobStatus::Queued),
+                executing_ms: job.time_in(JobStatus::Executing),
+                proving_ms: job.time_in(JobStatus::Proving),
+                verifying_ms: job.time_in(JobStatus::Verifying),
+                total_ms: job.total_ms(),
+            },
+            result: job.result.clone(),
+            error: job.error.clone(),
+        })
+    }
+
+    fn enter(&self, id: &str, status: JobStatus) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(id) {
+            job.stages.push((status, Instant::now()));
+        }
+    }
+
+    fn finish(&self, id: &str, outcome: Result<ProofResponse, String>) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(id) {
+            match outcome {
+                Ok(response) => {
+                    job.stages.push((JobStatus::Done, Instant::now()));
+                    job.result = Some(response);
+                }
+                Err(error) => {
+                    job.stages.push((JobStatus::Failed, Instant::now()));
+                    job.error = Some(error);
+                }
+            }
+        }
+    }
+}
@@ -1,18 +0,0 @@
-/* tslint:disable */
-/* eslint-disable */
-export const memory: WebAssembly.Memory;
-export const play_sound: (a: number, b: number) => void;
-export const toggle_sound: () => number;
-export const initialize_game: () => [number, number];
-export const log_to_proof_area: (a: number, b: number) => [number, number];
-export const show_sp1_proof_result: (a: number, b: number, c: number) => [number, number];
-export const start_game_from_js: () => void;
-export const __wbindgen_exn_store: (a: number) => void;
-export const __externref_table_alloc: () => number;
-export const __wbindgen_export_2: WebAssembly.Table;
-export const __wbindgen_export_3: WebAssembly.Table;
-export const __wbindgen_malloc: (a: number, b: number) => number;
-export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
-export const __externref_table_dealloc: (a: number) => void;
-export const _dyn_core__ops__function__FnMut_____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h9dedbc05d4cd90e1: (a: number, b: number) => void;
-export const __wbindgen_start: () => void;
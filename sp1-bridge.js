@@ -2,94 +2,264 @@
  * SP1 Bridge - JavaScript bridge between Memory Game and SP1 ZK Proof system
  */
 
+// Escape a value for interpolation into HTML. The proving service's answers are data, not markup
+function escapeHtml(value) {
+    return String(value)
+        .replace(/&/g, '&amp;')
+        .replace(/</g, '&lt;')
+        .replace(/>/g, '&gt;')
+        .replace(/"/g, '&quot;')
+        .replace(/'/g, '&#39;');
+}
+
 const SP1Bridge = {
-    // Main proof generation function
-    generateProof: async function(gameData) {
+    // SP1 backend address
+    apiUrl: 'http://localhost:3000',
+    
+    // How often to poll a proof job (milliseconds)
+    pollInterval: 2000,
+    
+    // Open a commit-reveal deck session for a game at `difficulty`: the backend commits to its
+    // seed and the difficulty, we send our seed, then the backend reveals its seed. Resolves to
+    // { sessionId, commitment, nonce, serverSeed } for the WASM game to re-deal the deck from both
+    // seeds, or null without a backend
+    openSession: async function(clientSeed, difficulty) {
+        try {
+            const sessionResponse = await fetch(`${this.apiUrl}/api/session`, {
+                method: 'POST',
+                headers: {
+                    'Content-Type': 'application/json',
+                },
+                body: JSON.stringify({ difficulty })
+            });
+            if (!sessionResponse.ok) {
+                throw new Error(`API error: ${sessionResponse.status}`);
+            }
+            const session = await sessionResponse.json();
+            
+            const revealResponse = await fetch(`${this.apiUrl}/api/session/${session.sessionId}/reveal`, {
+                method: 'POST',
+                headers: {
+                    'Content-Type': 'application/json',
+                },
+                body: JSON.stringify({ clientSeed })
+            });
+            if (!revealResponse.ok) {
+                throw new Error(`API error: ${revealResponse.status}`);
+            }
+            const reveal = await revealResponse.json();
+            
+            console.log("SP1Bridge: Deck session opened", session.sessionId);
+            return {
+                sessionId: session.sessionId,
+                commitment: session.commitment,
+                nonce: session.nonce,
+                serverSeed: reveal.serverSeed
+            };
+        } catch (error) {
+            // Without a session the game still works, but only simulated proofs are possible
+            console.warn("SP1Bridge: Could not open a deck session, using the local seed:", error);
+            return null;
+        }
+    },
+    
+    // Main proof generation function. `reporter` belongs to the game being proved: log(message),
+    // jobStatus(status, elapsedMs), result(success, hash), browserVerification(status, detail)
+    // and simulated() report to its proof panel, and the result card goes in reporter.proofLog.
+    // Resolves once the proof or the simulation is over; the reporter can't be used after that
+    generateProof: async function(gameData, reporter) {
         console.log("SP1Bridge: Proof generation started", gameData);
         
         // Show log in the proof area
-        if (window.log_to_proof_area) {
-            window.log_to_proof_area("SP1 Proof system initializing...");
-            window.log_to_proof_area(`Score: ${gameData.score}, Moves: ${gameData.moves}, Time: ${gameData.time}s`);
-            window.log_to_proof_area("Running SP1 ZK program...");
-        }
+        reporter.log("SP1 Proof system initializing...");
+        reporter.log(`Score: ${gameData.score}, Moves: ${gameData.moves}, Time: ${gameData.time}s`);
+        reporter.log("Running SP1 ZK program...");
         
         try {
             // Try to generate a real proof
-            const result = await this.generateRealProof(gameData);
+            const result = await this.generateRealProof(gameData, reporter);
             if (result.success) {
                 return result;
             }
             
             // Fall back to simulation if unsuccessful
-            window.log_to_proof_area("Switching to simulation mode...");
-            return this.simulateProofProcess(gameData);
+            reporter.log("Switching to simulation mode...");
+            return this.simulateProofProcess(gameData, reporter);
         } catch (error) {
             console.error("Error generating real proof:", error);
-            window.log_to_proof_area(`Error: ${error.message}`);
-            window.log_to_proof_area("Falling back to simulation mode...");
+            reporter.log(`Error: ${error.message}`);
+            reporter.log("Falling back to simulation mode...");
             
             // Fall back to simulation in case of error
-            return this.simulateProofProcess(gameData);
+            return this.simulateProofProcess(gameData, reporter);
         }
     },
     
     // Call the real SP1 proof API
-    generateRealProof: async function(gameData) {
-        if (window.log_to_proof_area) {
-            window.log_to_proof_area("Connecting to SP1 backend...");
-        }
+    generateRealProof: async function(gameData, reporter) {
+        reporter.log("Connecting to SP1 backend...");
         
         try {
+            // The proof is bound to the player who signs the transcript
+            const claim = await this.signTranscript(gameData, reporter);
+            
             // API call
-            const response = await fetch('http://localhost:3000/api/generate-proof', {
+            const response = await fetch(`${this.apiUrl}/api/generate-proof`, {
                 method: 'POST',
                 headers: {
                     'Content-Type': 'application/json',
                 },
-                body: JSON.stringify(gameData)
+                body: JSON.stringify({ ...gameData, ...claim })
             });
             
             if (!response.ok) {
-                throw new Error(`API error: ${response.status}`);
+                // The proving service explains rejected games in the error body
+                const failure = await response.json().catch(() => ({}));
+                throw new Error(failure.error || `API error: ${response.status}`);
             }
             
-            const result = await response.json();
+            // The service queues the proof and answers right away with a job id
+            const job = await response.json();
+            reporter.log(`Proof job queued: ${job.jobId}`);
             
-            if (window.log_to_proof_area) {
-                window.log_to_proof_area("SP1 Proof successfully generated!");
-                window.log_to_proof_area(`Proof Hash: ${result.proofHash}`);
-            }
+            const result = await this.pollProofJob(job.jobId, reporter);
+            
+            reporter.log("SP1 Proof successfully generated!");
+            reporter.log(`Proof Digest (SHA-256): ${result.proofHash}`);
+            reporter.log(`VKey Hash: ${result.vkeyHash}`);
+            reporter.log(`Public Values Hash: ${result.publicValuesHash}`);
+            
+            // Check the proof ourselves instead of trusting the service's word for it
+            const browserCheck = this.verifyInBrowser(result, reporter);
             
-            // Create visual result
-            this.createVisualProofResult(gameData, result.proofHash);
+            // Create visual result (with the values proven by SP1, decoded in the browser when
+            // the proof could be verified here)
+            const game = browserCheck.game;
+            const decoded = game ? {
+                score: game.score,
+                moves: game.moves,
+                time: game.time,
+                matchedPairs: game.matchedPairs,
+                difficulty: game.difficulty
+            } : {};
+            this.createVisualProofResult({
+                ...gameData,
+                ...result.gameData,
+                score: result.calculatedScore,
+                remainingTime: result.remainingTime,
+                ...decoded
+            }, result, browserCheck, reporter.proofLog);
             
             // Show final result
-            if (window.show_sp1_proof_result) {
-                window.show_sp1_proof_result(true, result.proofHash);
-            }
+            reporter.result(true, result.proofHash);
+            reporter.browserVerification(browserCheck.status, browserCheck.detail);
             
             return result;
         } catch (error) {
             console.error("API call failed:", error);
-            if (window.log_to_proof_area) {
-                window.log_to_proof_area(`API Error: ${error.message}`);
-            }
+            reporter.log(`API Error: ${error.message}`);
             throw error;
         }
     },
     
-    // Proof process simulation
-    simulateProofProcess: function(gameData) {
-        // New score calculation simulation
-        const TIME_LIMIT = 120;
-        const remaining_time = gameData.time < TIME_LIMIT ? TIME_LIMIT - gameData.time : 0;
-        const calculated_score = Math.max(0, remaining_time - gameData.moves);
+    // Sign the game transcript with the player's Ethereum wallet (personal_sign over the
+    // transcript hash from the proving service). The proof commits the signer's address.
+    signTranscript: async function(gameData, reporter) {
+        if (!window.ethereum) {
+            throw new Error("An Ethereum wallet is needed to claim a proven score");
+        }
+        const [player] = await window.ethereum.request({ method: 'eth_requestAccounts' });
+        
+        const response = await fetch(`${this.apiUrl}/api/transcript-hash`, {
+            method: 'POST',
+            headers: {
+                'Content-Type': 'application/json',
+            },
+            body: JSON.stringify(gameData)
+        });
+        if (!response.ok) {
+            const failure = await response.json().catch(() => ({}));
+            throw new Error(failure.error || `API error: ${response.status}`);
+        }
+        const { transcriptHash } = await response.json();
+        
+        reporter.log(`Sign the game with ${player} to claim it...`);
+        const signature = await window.ethereum.request({
+            method: 'personal_sign',
+            params: [transcriptHash, player]
+        });
+        
+        return { player, signature };
+    },
+    
+    // Verify a Groth16/PLONK proof in the browser with the game's wasm verifier, against the
+    // program vkey embedded in the wasm build. The vkey the proving service reports is never
+    // trusted: it says nothing about which program ran. Returns
+    // { status: 'verified' | 'failed' | 'unavailable', detail, game }
+    verifyInBrowser: function(result, reporter) {
+        if (!result.proofBytes) {
+            return {
+                status: 'unavailable',
+                detail: `${result.proofKind} proofs can only be verified by the proving service (run it with PROOF_KIND=groth16 or plonk)`
+            };
+        }
+        if (!reporter.verify) {
+            return { status: 'unavailable', detail: 'This build of the game has no proof verifier (the verifier feature is off)' };
+        }
+        if (!reporter.programVKey) {
+            return { status: 'unavailable', detail: 'No program vkey was pinned when this game was built (MEMORY_GAME_PROGRAM_VKEY)' };
+        }
+        
+        try {
+            const game = reporter.verify(result.proofKind, result.proofBytes, result.publicValuesBytes);
+            return {
+                status: 'verified',
+                detail: `score ${game.score} for ${game.player}`,
+                game
+            };
+        } catch (error) {
+            return { status: 'failed', detail: error.message || String(error) };
+        }
+    },
+    
+    // Poll a proof job until it is done (returns its result) or failed (throws)
+    pollProofJob: async function(jobId, reporter) {
+        let lastStatus = null;
+        
+        while (true) {
+            const response = await fetch(`${this.apiUrl}/api/jobs/${jobId}`);
+            if (!response.ok) {
+                throw new Error(`API error: ${response.status}`);
+            }
+            const job = await response.json();
+            
+            if (job.status !== lastStatus) {
+                lastStatus = job.status;
+                reporter.log(`Proof job ${job.status} (${Math.round(job.timings.totalMs / 1000)}s)`);
+            }
+            reporter.jobStatus(job.status, job.timings.totalMs);
+            
+            if (job.status === 'done') {
+                return job.result;
+            }
+            if (job.status === 'failed') {
+                throw new Error(job.error || 'Proof job failed');
+            }
+            
+            await new Promise(resolve => setTimeout(resolve, this.pollInterval));
+        }
+    },
+    
+    // Proof process simulation; resolves when the simulated result is shown
+    simulateProofProcess: function(gameData, reporter) {
+        // Score and remaining time come from the WASM game engine (game_core scoring)
+        const remaining_time = gameData.remainingTime;
+        const calculated_score = gameData.score;
         
         const steps = [
             { message: "Loading SP1 RISC-V program...", delay: 500 },
             { message: "Preparing game data for verification...", delay: 500 },
-            { message: `Input values: Moves=${gameData.moves}, Time=${gameData.time}s, Matched=${gameData.matchedPairs}`, delay: 1000 },
+            { message: `Input values: Difficulty=${gameData.difficulty}, Moves=${gameData.moves}, Time=${gameData.time}s, Matched=${gameData.matchedPairs}`, delay: 1000 },
             { message: "Validating game rules...", delay: 800 },
             { message: `Checking score calculation: Remaining Time (${remaining_time}) - Moves (${gameData.moves}) = ${calculated_score}`, delay: 1200 },
             { message: "Building SP1 ZK circuit...", delay: 1000 },
@@ -97,103 +267,135 @@ const SP1Bridge = {
             { message: "Generating cryptographic proof (2/3)...", delay: 1200 },
             { message: "Generating cryptographic proof (3/3)...", delay: 1200 },
             { message: "Verifying proof...", delay: 1000 },
-            { message: "Proof successfully generated and verified! (SIMULATION)", delay: 800 }
+            { message: "Simulation finished. No proof was generated, this result can't be verified.", delay: 800 }
         ];
         
         let currentStep = 0;
         
-        // Show steps sequentially
-        const processNextStep = () => {
-            if (currentStep < steps.length) {
-                if (window.log_to_proof_area) {
-                    window.log_to_proof_area(steps[currentStep].message);
-                }
+        return new Promise(resolve => {
+            // Show steps sequentially
+            const processNextStep = () => {
+                if (currentStep < steps.length) {
+                    reporter.log(steps[currentStep].message);
                 
-                setTimeout(() => {
-                    currentStep++;
-                    processNextStep();
-                }, steps[currentStep].delay);
-            } else {
-                // All steps completed, show the result
-                this.completeProof(gameData);
-            }
-        };
+                    setTimeout(() => {
+                        currentStep++;
+                        processNextStep();
+                    }, steps[currentStep].delay);
+                } else {
+                    // All steps completed, show the result
+                    this.completeProof(gameData, reporter);
+                    resolve();
+                }
+            };
         
-        // Start the first step
-        processNextStep();
+            // Start the first step
+            processNextStep();
+        });
     },
     
-    // Complete the proof process and show the result
-    completeProof: function(gameData) {
-        // Generate proof hash
-        const hash = this.generateProofHash(gameData);
+    // Complete the simulation and show the (unverifiable) result
+    completeProof: function(gameData, reporter) {
+        reporter.log("=== SIMULATED RESULT ===");
+        reporter.log("No proof, no digest: nothing here can be verified.");
+        reporter.log("========================");
         
-        // Show the result
-        if (window.log_to_proof_area) {
-            window.log_to_proof_area("=== PROOF RESULT ===");
-            window.log_to_proof_area(`Hash: ${hash}`);
-            window.log_to_proof_area("===================");
-        }
+        // Create visual elements for result display (no proof)
+        this.createVisualProofResult(gameData, null, null, reporter.proofLog);
         
-        // Create visual elements for result display
-        this.createVisualProofResult(gameData, hash);
-        
-        // Call show_sp1_proof_result function in WASM
-        if (window.show_sp1_proof_result) {
-            window.show_sp1_proof_result(true, hash);
-        }
+        // Mark the game's proof panel as simulated
+        reporter.simulated();
     },
     
-    // Create visual proof result
-    createVisualProofResult: function(gameData, hash) {
+    // Create visual proof result in `proofLog`. `proof` holds the proving service's digests, or is
+    // null for a simulation; `browserCheck` is the outcome of verifyInBrowser for a real proof, and
+    // the only thing that makes the result show as verified
+    createVisualProofResult: function(gameData, proof, browserCheck, proofLog) {
+        // Header and colors: simulated, or as far as the browser could check the proof
+        const verdicts = {
+            simulated: { icon: '⚠', title: 'Simulated - Not Verifiable', color: '#f39c12', background: 'rgba(243, 156, 18, 0.2)' },
+            verified: { icon: '✓', title: 'Proof Verified!', color: '#2ecc71', background: 'rgba(46, 204, 113, 0.2)' },
+            failed: { icon: '✗', title: 'Proof Verification Failed', color: '#e74c3c', background: 'rgba(231, 76, 60, 0.2)' },
+            unavailable: { icon: '?', title: 'Proof Not Verified in Your Browser', color: '#f39c12', background: 'rgba(243, 156, 18, 0.2)' }
+        };
+        const verdict = verdicts[proof ? browserCheck.status : 'simulated'] || verdicts.unavailable;
+        
         // Create div to display proof result
         const proofResultDiv = document.createElement('div');
-        proofResultDiv.id = 'proof-result';
+        proofResultDiv.className = 'proof-result';
         proofResultDiv.style.marginTop = '20px';
         proofResultDiv.style.padding = '15px';
-        proofResultDiv.style.backgroundColor = 'rgba(46, 204, 113, 0.2)';
         proofResultDiv.style.borderRadius = '8px';
-        proofResultDiv.style.border = '1px solid #2ecc71';
+        proofResultDiv.style.backgroundColor = verdict.background;
+        proofResultDiv.style.border = `1px solid ${verdict.color}`;
         
-        // TIME_LIMIT and new score calculation
-        const TIME_LIMIT = 120;
-        const remaining_time = gameData.time < TIME_LIMIT ? TIME_LIMIT - gameData.time : 0;
-        const calculated_score = Math.max(0, remaining_time - gameData.moves);
+        // Score and remaining time come from the WASM game engine or the SP1 proof
+        const remaining_time = gameData.remainingTime;
+        const calculated_score = gameData.score;
         
-        // Add result div to proof area
-        const proofLog = document.getElementById('proof-log');
-        if (proofLog) {
+        // Add result div to the proof panel (gone if the game was left or restarted meanwhile)
+        if (proofLog && proofLog.isConnected) {
             proofLog.appendChild(proofResultDiv);
             
-            // Create result content
-            const resultHTML = `
+            // Header and proof identifiers: only a real proof has any. Everything from the
+            // proving service is escaped
+            const headerHTML = `
                 <div style="text-align: center; margin-bottom: 10px;">
-                    <span style="font-size: 24px; color: #2ecc71;">✓</span>
-                    <span style="font-weight: bold; font-size: 18px; color: #2ecc71;"> Proof Verified!</span>
+                    <span style="font-size: 24px; color: ${verdict.color};">${verdict.icon}</span>
+                    <span style="font-weight: bold; font-size: 18px; color: ${verdict.color};"> ${verdict.title}</span>
+                </div>`;
+            const browserLabels = { verified: '✓ Verified', failed: '✗ Failed', unavailable: 'Not available' };
+            const proofHTML = proof ? `
+                <div style="margin-top: 5px; word-break: break-all;">
+                    <span style="font-weight: bold;">Browser Verification:</span> 
+                    <span style="color: ${verdict.color};">${browserLabels[browserCheck.status]} (${escapeHtml(browserCheck.detail)})</span>
+                </div>
+                <div style="margin-top: 5px; word-break: break-all;">
+                    <span style="font-weight: bold;">Player:</span> 
+                    <span style="font-family: monospace; color: #3498db;">${escapeHtml(proof.publicValues.player)}</span>
+                </div>
+                <div style="margin-top: 5px; word-break: break-all;">
+                    <span style="font-weight: bold;">Proof Digest (SHA-256):</span> 
+                    <span style="font-family: monospace; color: #3498db;">${escapeHtml(proof.proofHash)}</span>
+                </div>
+                <div style="margin-top: 5px; word-break: break-all;">
+                    <span style="font-weight: bold;">VKey Hash:</span> 
+                    <span style="font-family: monospace; color: #3498db;">${escapeHtml(proof.vkeyHash)}</span>
                 </div>
+                <div style="margin-top: 5px; word-break: break-all;">
+                    <span style="font-weight: bold;">Public Values Hash:</span> 
+                    <span style="font-family: monospace; color: #3498db;">${escapeHtml(proof.publicValuesHash)}</span>
+                </div>` : `
+                <div style="margin-top: 5px; color: #f39c12;">
+                    No proof was generated (no wallet signature, or the proving service was unavailable), so this score can't be verified.
+                </div>`;
+            
+            // Create result content
+            const resultHTML = `
+                ${headerHTML}
                 <div style="margin-bottom: 1px;">
-                    <span style="font-weight: bold;">Score:</span> ${calculated_score} (Remaining Time - Moves)
+                    <span style="font-weight: bold;">Score:</span> ${escapeHtml(calculated_score)} (Remaining Time - Moves)
                 </div>
                 <div style="margin-bottom: 1px;">
-                    <span style="font-weight: bold;">Remaining Time:</span> ${remaining_time} seconds
+                    <span style="font-weight: bold;">Remaining Time:</span> ${escapeHtml(remaining_time)} seconds
                 </div>
                 <div style="margin-bottom: 2px;">
-                    <span style="font-weight: bold;">Matched Pairs:</span> ${gameData.matchedPairs}
+                    <span style="font-weight: bold;">Difficulty:</span> ${escapeHtml(gameData.difficulty || 'normal')}
                 </div>
                 <div style="margin-bottom: 2px;">
-                    <span style="font-weight: bold;">Moves:</span> ${gameData.moves}
+                    <span style="font-weight: bold;">Matched Pairs:</span> ${escapeHtml(gameData.matchedPairs)}
                 </div>
                 <div style="margin-bottom: 2px;">
-                    <span style="font-weight: bold;">Game Time:</span> ${gameData.time} seconds
+                    <span style="font-weight: bold;">Moves:</span> ${escapeHtml(gameData.moves)}
                 </div>
-                <div style="margin-top: 5px; word-break: break-all;">
-                    <span style="font-weight: bold;">Proof Hash:</span> 
-                    <span style="font-family: monospace; color: #3498db;">${hash}</span>
+                <div style="margin-bottom: 2px;">
+                    <span style="font-weight: bold;">Game Time:</span> ${escapeHtml(gameData.time)} seconds
                 </div>
+                ${proofHTML}
                 
                 <!-- Share Button -->
                 <div style="margin-top: 15px; text-align: center;">
-                    <button id="share-x-button" style="
+                    <button class="share-x-button" style="
                         background-color: #000;
                         color: white;
                         border: none;
@@ -218,31 +420,11 @@ const SP1Bridge = {
             proofResultDiv.innerHTML = resultHTML;
             
             // Add click event to share button
-            setTimeout(() => {
-                const shareButton = document.getElementById('share-x-button');
-                if (shareButton) {
-                    shareButton.addEventListener('click', () => this.shareOnX(gameData, calculated_score));
-                }
-            }, 100);
+            const shareButton = proofResultDiv.querySelector('.share-x-button');
+            shareButton.addEventListener('click', () => this.shareOnX(gameData, calculated_score));
         }
     },
     
-    // Generate proof hash (simulation of a real proof hash)
-    generateProofHash: function(gameData) {
-        // Used for TIME_LIMIT and new score calculation
-        const TIME_LIMIT = 120;
-        const remaining_time = gameData.time < TIME_LIMIT ? TIME_LIMIT - gameData.time : 0;
-        const calculated_score = Math.max(0, remaining_time - gameData.moves);
-        
-        // Create a random hash
-        const scoreHex = calculated_score.toString(16).padStart(4, '0');
-        const movesHex = gameData.moves.toString(16).padStart(4, '0');
-        const timeHex = gameData.time.toString(16).padStart(4, '0');
-        const randomPart = Math.floor(Math.random() * 0x10000000000000).toString(16).padStart(14, '0');
-        
-        return `0x${scoreHex}${movesHex}${timeHex}${randomPart}`;
-    },
-    
     // Share score on X (Twitter)
     shareOnX: function(gameData, score) {
         const shareText = `I scored ${score} points in @SuccinctLabs Memory Game. Play it yourself! memory-game-sp1.vercel.app`;
@@ -261,8 +443,8 @@ const SP1Bridge = {
 window.SP1Bridge = SP1Bridge;
 
 // Global proof generation function
-window.generateSP1Proof = function(gameData) {
-    return SP1Bridge.generateProof(gameData);
+window.generateSP1Proof = function(gameData, reporter) {
+    return SP1Bridge.generateProof(gameData, reporter);
 };
 
 console.log("SP1Bridge loaded - Memory Game ZK integration ready!");
\ No newline at end of file
@@ -1,1066 +1,1693 @@
 // lib.rs
 use wasm_bindgen::prelude::*;
-use web_sys::{Document, HtmlElement};
+use web_sys::{Document, Element, HtmlElement, HtmlSelectElement};
 use js_sys::Math;
 use wasm_bindgen::JsCast;
-use std::cell::RefCell;
+use wasm_bindgen_futures::JsFuture;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use memory_game_core::{commitment, deck, hex, transcript};
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::{Command, Event, Game, Phase};
+use memory_game_core::transcript::{Action, Transcript};
+
+mod replay;
+#[cfg(feature = "verifier")]
+mod verifier;
 
 // Constants
-const TIME_LIMIT: usize = 120; // 120 seconds time limit
+const CARD_IMAGE_COUNT: u32 = 8; // card-0.png .. card-7.png (larger boards tint them)
+const MAX_CARD_SIZE: u32 = 120; // Card size in pixels on small boards
+const BOARD_WIDTH: u32 = 984; // Game board content area in pixels (without padding)
+const BOARD_HEIGHT: u32 = 440;
+const BOARD_STYLE: &str = "height: 480px; display: flex; flex-wrap: wrap; justify-content: center; align-items: center; gap: 20px; padding: 20px;";
+const FOOTER_STYLE: &str = "height: 138px; display: flex; justify-content: center; align-items: center; gap: 30px;";
 
 // Asset paths
 const IMAGE_PATH: &str = "assets/images/";
 const SOUND_PATH: &str = "assets/sounds/"; // For sound files
 
+// Commit-reveal deck session opened by the backend
+struct DeckSession {
+    id: String,
+    commitment: commitment::Commitment,
+    nonce: [u8; 32], // Issued by the backend; the proof commits it so it can only be used once
+}
+
 // Game state
 struct GameState {
-    cards: Vec<usize>,
-    flipped_cards: Vec<usize>,
-    matched_pairs: Vec<usize>,
-    moves: usize,
-    timer: usize,
-    score: usize,
-    game_started: bool,
-    game_over: bool,
-    timer_interval_id: Option<i32>,
-    is_checking: bool,
+    difficulty: Difficulty, // Board size and time limit of the next deal
+    seed: u64, // Deck shuffle seed (the deck can be rebuilt from it)
+    client_seed: u64, // Our contribution to the deck seed
+    session: Option<DeckSession>, // None until the backend reveals its seed
+    game: Game, // Rules and progress (memory_game_core engine)
+    transcript: Transcript, // Everything that happened on this board since the current deck was dealt, with timestamps
+}
+
+// An event handler owned by a game; dropping it releases the JavaScript function
+type Listener = Closure<dyn FnMut()>;
+
+// A running setInterval, cleared when dropped together with its callback
+struct Interval {
+    id: i32,
+    _callback: Listener,
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            window.clear_interval_with_handle(self.id);
+        }
+    }
+}
+
+// A pending setTimeout, cancelled when dropped together with its callback
+struct Timeout {
+    id: i32,
+    _callback: Listener,
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            window.clear_timeout_with_handle(self.id);
+        }
+    }
+}
+
+// Elements of a mounted game
+struct GameView {
+    root: Element,
+    timer: Element,
+    moves: Element,
+    score: Element,
+    board: Element,
+    footer: Element,
+    start_button: Element,
+    reset_button: Element,
+    prove_button: Element,
+    difficulty_select: HtmlSelectElement,
+}
+
+// A dealt card and its click handler
+struct Card {
+    element: Element,
+    _on_click: Listener,
+}
+
+// The proof panel shown in place of the cards
+struct ProofPanel {
+    header: Element,
+    log: Element,
+    _listeners: Vec<Listener>,
 }
 
-// Sound settings
-struct SoundSettings {
-    enabled: bool,
+// Everything one mounted game owns
+struct GameInstance {
+    container: Element, // Where the game is mounted; game events are dispatched on it
+    view: GameView,
+    state: RefCell<GameState>,
+    sound_enabled: Cell<bool>,
+    callbacks: RefCell<Vec<(&'static str, js_sys::Function)>>, // Registered with MemoryGame::on
+    controls: RefCell<Vec<Listener>>, // Buttons and difficulty selector
+    cards: RefCell<Vec<Card>>,
+    proof_panel: RefCell<Option<ProofPanel>>,
+    timer: RefCell<Option<Interval>>,
+    pending_check: RefCell<Option<Timeout>>, // Match check after two flips
+    replay: RefCell<Option<replay::Replay>>, // Set while a recorded game is shown instead
+}
+
+// A memory game mounted in a container element. Any number of games can share a page; each one
+// owns its state, and `destroy` removes it and releases its timer and event handlers.
+#[wasm_bindgen]
+pub struct MemoryGame {
+    game: Rc<GameInstance>,
 }
 
-// Global RefCell to store the game state
 thread_local! {
-    static GAME_STATE: RefCell<GameState> = RefCell::new(GameState {
-        cards: Vec::new(),
-        flipped_cards: Vec::new(),
-        matched_pairs: Vec::new(),
-        moves: 0,
-        timer: 0,
-        score: 0,
-        game_started: false,
-        game_over: false,
-        timer_interval_id: None,
-        is_checking: false,
-    });
-    
-    // Thread-local variable for sound settings
-    static SOUND_SETTINGS: RefCell<SoundSettings> = RefCell::new(SoundSettings {
-        enabled: true,
-    });
+    // The game created by initialize_game, for the free functions index.html uses
+    static DEFAULT_GAME: RefCell<Option<MemoryGame>> = const { RefCell::new(None) };
 }
 
-// Sound playing function
 #[wasm_bindgen]
-pub fn play_sound(sound_name: &str) {
-    // Don't play if sound is disabled
-    let enabled = SOUND_SETTINGS.with(|settings| {
-        settings.borrow().enabled
-    });
-    
-    if !enabled {
+impl MemoryGame {
+    // Mount a new game in `container`. Options (all optional): { difficulty: "easy" | "normal" |
+    // "hard" | "expert", sound: true | false }
+    #[wasm_bindgen(constructor)]
+    pub fn new(container: &Element, options: JsValue) -> Result<MemoryGame, JsValue> {
+        let window = web_sys::window().expect("No global window");
+        let document = window.document().expect("No global document");
+
+        let (difficulty, sound_enabled) = read_options(&options)?;
+
+        // Create the game board inside the container
+        let view = create_game_board(&document, difficulty)?;
+        container.append_child(&view.root)?;
+
+        let game = Rc::new(GameInstance {
+            container: container.clone(),
+            view,
+            state: RefCell::new(GameState {
+                difficulty,
+                seed: 0,
+                client_seed: 0,
+                session: None,
+                game: Game::default(),
+                transcript: Transcript::new(js_sys::Date::now() as u64),
+            }),
+            sound_enabled: Cell::new(sound_enabled),
+            callbacks: RefCell::new(Vec::new()),
+            controls: RefCell::new(Vec::new()),
+            cards: RefCell::new(Vec::new()),
+            proof_panel: RefCell::new(None),
+            timer: RefCell::new(None),
+            pending_check: RefCell::new(None),
+            replay: RefCell::new(None),
+        });
+        attach_controls(&game);
+
+        // Create cards but make them unclickable before the game starts
+        prepare_cards(&game);
+
+        Ok(MemoryGame { game })
+    }
+
+    // Start the game on the dealt deck. After a finished game this deals a new deck (with a new
+    // deck session) instead, to be started with another call.
+    pub fn start(&self) {
+        start_game(&self.game);
+    }
+
+    // Stop the game and deal a new deck
+    pub fn reset(&self) {
+        reset_game(&self.game);
+    }
+
+    // Remove the game from its container and release its timer and event handlers
+    pub fn destroy(self) {
+        let game = self.game;
+        game.timer.take();
+        game.pending_check.take();
+        game.replay.take();
+        game.proof_panel.take();
+        game.cards.borrow_mut().clear();
+        game.controls.borrow_mut().clear();
+        game.callbacks.borrow_mut().clear();
+        game.view.root.remove();
+    }
+
+    // Turn sound on or off, returns whether it is now on
+    #[wasm_bindgen(js_name = toggleSound)]
+    pub fn toggle_sound(&self) -> bool {
+        self.game.sound_enabled.set(!self.game.sound_enabled.get());
+        self.game.sound_enabled.get()
+    }
+
+    // Seed of the current deck as 16 hex digits
+    pub fn seed(&self) -> String {
+        deck::format_seed(self.game.state.borrow().seed)
+    }
+
+    // Current difficulty name ("easy", "normal", "hard" or "expert")
+    pub fn difficulty(&self) -> String {
+        self.game.state.borrow().difficulty.name().to_string()
+    }
+
+    // Change the difficulty and deal a new deck (not while a game is running)
+    #[wasm_bindgen(js_name = setDifficulty)]
+    pub fn set_difficulty(&self, name: &str) -> Result<(), JsValue> {
+        set_game_difficulty(&self.game, name)
+    }
+
+    // Call `callback` with the detail of every `event` (see MemoryGameEventMap)
+    #[wasm_bindgen(skip_typescript)]
+    pub fn on(&self, event: &str, callback: js_sys::Function) -> Result<(), JsValue> {
+        let name = GAME_EVENT_NAMES.into_iter().find(|&name| name == event).ok_or_else(|| format!("Unknown game event: {}", event))?;
+        self.game.callbacks.borrow_mut().push((name, callback));
+        Ok(())
+    }
+
+    // Everything that happened on this board since the current deal as JSON: { version, startedAt, entries: [{ at, action, ... }] }
+    #[wasm_bindgen(js_name = transcriptJson)]
+    pub fn transcript_json(&self) -> String {
+        transcript_json(&self.game.state.borrow().transcript)
+    }
+
+    // The same transcript in the compact binary encoding (game_core::transcript)
+    #[wasm_bindgen(js_name = transcriptBytes)]
+    pub fn transcript_bytes(&self) -> Vec<u8> {
+        self.game.state.borrow().transcript.encode()
+    }
+
+    // Stop calling a callback registered with `on`
+    #[wasm_bindgen(skip_typescript)]
+    pub fn off(&self, event: &str, callback: &js_sys::Function) {
+        self.game.callbacks.borrow_mut().retain(|(name, registered)| !(*name == event && registered == callback));
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const GAME_EVENTS_TS: &str = r#"
+export interface MemoryGameEventMap {
+    gameStarted: { difficulty: string; seed: string; timeLimit: number };
+    cardFlipped: { index: number; value: number };
+    matchFound: { first: number; second: number; value: number; matchedPairs: number };
+    mismatch: { first: number; second: number };
+    tick: { elapsed: number; remaining: number };
+    gameWon: { score: number; moves: number; time: number };
+    gameLost: { moves: number; time: number; matchedPairs: number };
+    proofRequested: { score: number; moves: number; time: number; difficulty: string; sessionId: string | null };
+}
+
+export interface MemoryGame {
+    on<K extends keyof MemoryGameEventMap>(event: K, callback: (detail: MemoryGameEventMap[K]) => void): void;
+    off<K extends keyof MemoryGameEventMap>(event: K, callback: (detail: MemoryGameEventMap[K]) => void): void;
+}
+"#;
+
+// Names of the game events a callback can be registered for
+const GAME_EVENT_NAMES: [&str; 8] = ["gameStarted", "cardFlipped", "matchFound", "mismatch", "tick", "gameWon", "gameLost", "proofRequested"];
+
+// Events published to the host page, through callbacks registered with MemoryGame::on and as
+// CustomEvents (with the same name and detail) on the game's container
+enum GameEvent {
+    GameStarted { difficulty: Difficulty, seed: u64, time_limit: u32 },
+    CardFlipped { index: usize, value: u32 },
+    MatchFound { first: usize, second: usize, value: u32, matched_pairs: u32 },
+    Mismatch { first: usize, second: usize },
+    Tick { elapsed: u32, remaining: u32 },
+    GameWon { score: u32, moves: u32, time: u32 },
+    GameLost { moves: u32, time: u32, matched_pairs: u32 },
+    ProofRequested { score: u32, moves: u32, time: u32, difficulty: Difficulty, session_id: Option<String> },
+}
+
+impl GameEvent {
+    // The host-page event for an engine event, with the game's progress filled in
+    fn from_engine(event: &Event, game: &Game, seed: u64) -> GameEvent {
+        match *event {
+            Event::Started => GameEvent::GameStarted { difficulty: game.difficulty(), seed, time_limit: game.difficulty().time_limit() },
+            Event::CardFlipped { index, value } => GameEvent::CardFlipped { index, value },
+            Event::MatchFound { first, second, value } => GameEvent::MatchFound { first, second, value, matched_pairs: game.matched_pairs() },
+            Event::Mismatch { first, second } => GameEvent::Mismatch { first, second },
+            Event::Tick { elapsed, remaining } => GameEvent::Tick { elapsed, remaining },
+            Event::Won { score } => GameEvent::GameWon { score, moves: game.moves(), time: game.elapsed() },
+            Event::Lost => GameEvent::GameLost { moves: game.moves(), time: game.elapsed(), matched_pairs: game.matched_pairs() },
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            GameEvent::GameStarted { .. } => "gameStarted",
+            GameEvent::CardFlipped { .. } => "cardFlipped",
+            GameEvent::MatchFound { .. } => "matchFound",
+            GameEvent::Mismatch { .. } => "mismatch",
+            GameEvent::Tick { .. } => "tick",
+            GameEvent::GameWon { .. } => "gameWon",
+            GameEvent::GameLost { .. } => "gameLost",
+            GameEvent::ProofRequested { .. } => "proofRequested",
+        }
+    }
+
+    // The event detail, shaped as in MemoryGameEventMap
+    fn detail(&self) -> js_sys::Object {
+        let fields: Vec<(&str, JsValue)> = match self {
+            GameEvent::GameStarted { difficulty, seed, time_limit } => vec![
+                ("difficulty", difficulty.name().into()),
+                ("seed", deck::format_seed(*seed).into()),
+                ("timeLimit", (*time_limit).into()),
+            ],
+            GameEvent::CardFlipped { index, value } => vec![
+                ("index", (*index as u32).into()),
+                ("value", (*value).into()),
+            ],
+            GameEvent::MatchFound { first, second, value, matched_pairs } => vec![
+                ("first", (*first as u32).into()),
+                ("second", (*second as u32).into()),
+                ("value", (*value).into()),
+                ("matchedPairs", (*matched_pairs).into()),
+            ],
+            GameEvent::Mismatch { first, second } => vec![
+                ("first", (*first as u32).into()),
+                ("second", (*second as u32).into()),
+            ],
+            GameEvent::Tick { elapsed, remaining } => vec![
+                ("elapsed", (*elapsed).into()),
+                ("remaining", (*remaining).into()),
+            ],
+            GameEvent::GameWon { score, moves, time } => vec![
+                ("score", (*score).into()),
+                ("moves", (*moves).into()),
+                ("time", (*time).into()),
+            ],
+            GameEvent::GameLost { moves, time, matched_pairs } => vec![
+                ("moves", (*moves).into()),
+                ("time", (*time).into()),
+                ("matchedPairs", (*matched_pairs).into()),
+            ],
+            GameEvent::ProofRequested { score, moves, time, difficulty, session_id } => vec![
+                ("score", (*score).into()),
+                ("moves", (*moves).into()),
+                ("time", (*time).into()),
+                ("difficulty", difficulty.name().into()),
+                ("sessionId", session_id.as_deref().map_or(JsValue::NULL, JsValue::from)),
+            ],
+        };
+
+        let detail = js_sys::Object::new();
+        for (key, value) in fields {
+            let _ = js_sys::Reflect::set(&detail, &key.into(), &value);
+        }
+        detail
+    }
+}
+
+// Send an event to the game's callbacks and dispatch it on its container
+fn publish(game: &GameInstance, event: GameEvent) {
+    let name = event.name();
+    let detail = event.detail();
+
+    // Callbacks may register or remove callbacks, so call a snapshot of them
+    let callbacks: Vec<js_sys::Function> = game.callbacks.borrow().iter()
+        .filter(|(registered, _)| *registered == name)
+        .map(|(_, callback)| callback.clone())
+        .collect();
+    for callback in callbacks {
+        // A failing callback must not stop the game
+        if let Err(error) = callback.call1(&JsValue::NULL, &detail) {
+            web_sys::console::error_2(&format!("{} callback failed:", name).into(), &error);
+        }
+    }
+
+    let init = web_sys::CustomEventInit::new();
+    init.set_detail(&detail);
+    init.set_bubbles(true);
+    if let Ok(dom_event) = web_sys::CustomEvent::new_with_event_init_dict(name, &init) {
+        game.container.dispatch_event(&dom_event).ok();
+    }
+}
+
+// Record an event of the game engine in the transcript and publish it
+fn publish_engine_event(game: &GameInstance, event: &Event) {
+    record(game, Action::from_event(event));
+
+    let event = {
+        let game_state = game.state.borrow();
+        GameEvent::from_engine(event, &game_state.game, game_state.seed)
+    };
+    publish(game, event);
+}
+
+// Difficulty and sound setting from the options object of MemoryGame::new
+fn read_options(options: &JsValue) -> Result<(Difficulty, bool), JsValue> {
+    if options.is_undefined() || options.is_null() {
+        return Ok((Difficulty::default(), true));
+    }
+
+    let difficulty = match js_sys::Reflect::get(options, &"difficulty".into())?.as_string() {
+        Some(name) => Difficulty::from_name(&name).ok_or_else(|| format!("Unknown difficulty: {}", name))?,
+        None => Difficulty::default(),
+    };
+    let sound_enabled = js_sys::Reflect::get(options, &"sound".into())?.as_bool().unwrap_or(true);
+
+    Ok((difficulty, sound_enabled))
+}
+
+// Decode a binary transcript (MemoryGame.transcriptBytes) to the JSON of MemoryGame.transcriptJson
+#[wasm_bindgen]
+pub fn transcript_bytes_to_json(bytes: &[u8]) -> Result<String, JsValue> {
+    let transcript = Transcript::decode(bytes).ok_or("Invalid transcript")?;
+    Ok(transcript_json(&transcript))
+}
+
+fn transcript_json(transcript: &Transcript) -> String {
+    let entries: Vec<String> = transcript.entries.iter().map(|entry| {
+        let operands = match entry.action {
+            Action::Deal { difficulty, seed } => format!(r#","difficulty":"{}","seed":"{}""#, difficulty.name(), deck::format_seed(seed)),
+            Action::Flip { index } => format!(r#","index":{}"#, index),
+            Action::Match { first, second } | Action::Mismatch { first, second } => format!(r#","first":{},"second":{}"#, first, second),
+            Action::Tick { elapsed } => format!(r#","elapsed":{}"#, elapsed),
+            Action::Start | Action::Won | Action::Lost | Action::Reset => String::new(),
+        };
+        format!(r#"{{"at":{},"action":"{}"{}}}"#, entry.at_ms, entry.action.name(), operands)
+    }).collect();
+
+    format!(r#"{{"version":{},"startedAt":{},"entries":[{}]}}"#, transcript::VERSION, transcript.started_at_ms, entries.join(","))
+}
+
+// Add an action to the game's transcript, timestamped now
+fn record(game: &GameInstance, action: Action) {
+    let mut game_state = game.state.borrow_mut();
+    let at_ms = (js_sys::Date::now() as u64).saturating_sub(game_state.transcript.started_at_ms);
+    game_state.transcript.record(at_ms, action);
+}
+
+// Run `handler` with the default game, if initialize_game created one
+fn with_default_game<R>(handler: impl FnOnce(&MemoryGame) -> R) -> Option<R> {
+    DEFAULT_GAME.with(|default_game| default_game.borrow().as_ref().map(handler))
+}
+
+// Play a sound file once
+fn play_audio(sound_name: &str) {
+    let Ok(audio) = web_sys::HtmlAudioElement::new_with_src(&format!("{}{}", SOUND_PATH, sound_name)) else {
         return;
+    };
+
+    // Browsers refuse to play before the first user interaction; that's fine for sound effects
+    if let Ok(playback) = audio.play() {
+        wasm_bindgen_futures::spawn_local(async move {
+            JsFuture::from(playback).await.ok();
+        });
     }
-    
-    let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    // Create audio element
-    if let Ok(audio_element) = document.create_element("audio") {
-        audio_element.set_attribute("src", &format!("{}{}", SOUND_PATH, sound_name)).ok();
-        audio_element.set_attribute("autoplay", "true").ok();
-        
-        // Clone the audio element for the closure
-        let audio_element_clone = audio_element.clone();
-        
-        // Remove from DOM when playback is complete
-        let cleanup_closure = Closure::wrap(Box::new(move || {
-            if let Some(parent) = audio_element_clone.parent_node() {
-                parent.remove_child(&audio_element_clone).ok();
-            }
-        }) as Box<dyn FnMut()>);
-        
-        audio_element
-            .dyn_ref::<web_sys::HtmlAudioElement>()
-            .expect("Not an HtmlAudioElement")
-            .set_onended(Some(cleanup_closure.as_ref().unchecked_ref()));
-        
-        cleanup_closure.forget();
-        
-        // Add audio element to the body
-        document.body().unwrap().append_child(&audio_element).ok();
+}
+
+impl GameInstance {
+    fn play_sound(&self, sound_name: &str) {
+        // Don't play if sound is disabled
+        if self.sound_enabled.get() {
+            play_audio(sound_name);
+        }
     }
 }
 
-// Toggle sound settings
+// Sound playing function (follows the default game's sound setting)
+#[wasm_bindgen]
+pub fn play_sound(sound_name: &str) {
+    if with_default_game(|game| game.game.sound_enabled.get()).unwrap_or(true) {
+        play_audio(sound_name);
+    }
+}
+
+// Toggle sound settings of the default game
 #[wasm_bindgen]
 pub fn toggle_sound() -> bool {
-    SOUND_SETTINGS.with(|settings| {
-        let mut sound_settings = settings.borrow_mut();
-        sound_settings.enabled = !sound_settings.enabled;
-        sound_settings.enabled
-    })
+    with_default_game(MemoryGame::toggle_sound).unwrap_or(false)
 }
 
+// Mount the default game in a #game-container element at the end of the body
 #[wasm_bindgen]
 pub fn initialize_game() -> Result<(), JsValue> {
     // Get DOM elements
     let window = web_sys::window().expect("No global window");
     let document = window.document().expect("No global document");
-    
-    // Create the game board
-    create_game_board(&document)?;
-    
-    // Create cards but make them unclickable before the game starts
-    prepare_cards(&document);
-    
+
+    // Calling this again replaces the game in the same container
+    let container = match document.get_element_by_id("game-container") {
+        Some(container) => container,
+        None => {
+            let container = document.create_element("div")?;
+            container.set_id("game-container");
+            document.body().unwrap().append_child(&container)?;
+            container
+        }
+    };
+
+    let game = MemoryGame::new(&container, JsValue::UNDEFINED)?;
+    let instance = game.game.clone();
+    if let Some(previous) = DEFAULT_GAME.with(|default_game| default_game.replace(Some(game))) {
+        previous.destroy();
+    }
+
+    // A shared link opens its replay or challenge (#replay=CODE or #challenge=CODE)
+    let fragment = window.location().hash().unwrap_or_default();
+    if let Err(error) = replay::open_fragment(&instance, &fragment) {
+        let message = error.as_string().unwrap_or_default();
+        web_sys::console::warn_1(&format!("Could not open the shared game: {}", message).into());
+        window.alert_with_message(&format!("Could not open the shared game: {}", message)).ok();
+    }
+
     // Notify about asset folders
     web_sys::console::log_1(&"Asset folders need to be created:".into());
     web_sys::console::log_1(&format!("- {} (For card images)", IMAGE_PATH).into());
     web_sys::console::log_1(&format!("- {} (For sound effects)", SOUND_PATH).into());
-    
+
     Ok(())
 }
 
-fn prepare_cards(document: &Document) {
-    // Create 8 pairs of cards (0-7, each one twice)
-    let mut cards: Vec<usize> = (0..8).flat_map(|i| vec![i, i]).collect();
-    
-    // Shuffle the cards
-    for i in (1..cards.len()).rev() {
-        let j = (Math::random() * (i as f64 + 1.0)) as usize;
-        cards.swap(i, j);
+fn prepare_cards(game: &Rc<GameInstance>) {
+    // Pick a fresh client seed (two 32-bit halves, Math.random only has 53 bits of precision)
+    let client_seed = (((Math::random() * 4294967296.0) as u64) << 32) | (Math::random() * 4294967296.0) as u64;
+
+    {
+        let mut game_state = game.state.borrow_mut();
+        game_state.client_seed = client_seed;
+        game_state.session = None;
     }
-    
-    // Update game state
-    GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        game_state.cards = cards;
-        game_state.game_started = false; // Game hasn't started yet
-        game_state.game_over = false;
-    });
-    
+
+    // Deal from the client seed alone until the backend reveals its seed
+    deal_cards(game, client_seed);
+
+    // Ask the backend for a commit-reveal session for this deck
+    request_deck_session(game, client_seed);
+}
+
+fn deal_cards(game: &Rc<GameInstance>, seed: u64) {
+    // Create the difficulty's pairs of cards (each one twice), shuffled from the seed
+    let difficulty = game.state.borrow().difficulty;
+    let cards = deck::shuffled_deck(seed, difficulty.pairs());
+
+    // Update game state (the game hasn't started yet); the transcript only keeps this deck's game
+    {
+        let mut game_state = game.state.borrow_mut();
+        game_state.seed = seed;
+        game_state.game = Game::new(difficulty, cards);
+        game_state.transcript = Transcript::new(js_sys::Date::now() as u64);
+    }
+    record(game, Action::Deal { difficulty, seed });
+
     // Create cards visually
-    render_game_board(document);
+    render_game_board(game);
 }
 
-fn start_game() {
-    let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    // Start the game
-    GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        
-        // Reset the game
-        game_state.flipped_cards.clear();
-        game_state.matched_pairs.clear();
-        game_state.moves = 0;
-        game_state.timer = 0;
-        game_state.score = 0;
-        game_state.is_checking = false;
-        game_state.game_over = false;
-        
-        // Start a new game
-        game_state.game_started = true;
+// Typed bindings to window.SP1Bridge (sp1-bridge.js)
+#[wasm_bindgen]
+extern "C" {
+    type SP1Bridge;
+
+    // Opens a commit-reveal session for a game at `difficulty`; resolves to { sessionId,
+    // commitment, nonce, serverSeed }, or null if the backend is unavailable
+    #[wasm_bindgen(method, js_name = openSession)]
+    fn open_session(this: &SP1Bridge, client_seed: &str, difficulty: &str) -> js_sys::Promise;
+
+    // Proves a finished game, reporting progress and the result to `reporter` (see ProofReporter);
+    // resolves once the proof, or the simulation standing in for it, is over
+    #[wasm_bindgen(method, js_name = generateProof)]
+    fn generate_proof(this: &SP1Bridge, game_data: &js_sys::Object, reporter: &js_sys::Object) -> js_sys::Promise;
+}
+
+// The bridge, if sp1-bridge.js is loaded
+fn sp1_bridge() -> Option<SP1Bridge> {
+    let window = web_sys::window()?;
+    let bridge = js_sys::Reflect::get(&window, &"SP1Bridge".into()).ok()?;
+    if bridge.is_object() {
+        Some(bridge.unchecked_into())
+    } else {
+        None
+    }
+}
+
+// Call SP1Bridge.openSession(clientSeed, difficulty) if the bridge is loaded (offline games keep
+// the local seed)
+fn request_deck_session(game: &Rc<GameInstance>, client_seed: u64) {
+    let Some(bridge) = sp1_bridge() else { return };
+    let difficulty = game.state.borrow().difficulty;
+    let session = JsFuture::from(bridge.open_session(&deck::format_seed(client_seed), difficulty.name()));
+    let game = Rc::downgrade(game);
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let session = match session.await {
+            Ok(session) if session.is_object() => session,
+            Ok(_) => return, // The bridge already warned that the backend is unavailable
+            Err(error) => {
+                web_sys::console::warn_2(&"Deck session failed:".into(), &error);
+                return;
+            }
+        };
+
+        // The game may have been destroyed while the session was opening
+        let Some(game) = game.upgrade() else { return };
+
+        let field = |name: &str| {
+            js_sys::Reflect::get(&session, &name.into()).ok().and_then(|value| value.as_string()).unwrap_or_default()
+        };
+        if let Err(error) = apply_deck_session(&game, &field("sessionId"), &field("commitment"), &field("nonce"), &field("serverSeed"), client_seed, difficulty) {
+            web_sys::console::warn_2(&"Deck session rejected:".into(), &error);
+        }
+    });
+}
+
+// Seed of the default game's deck as 16 hex digits
+#[wasm_bindgen]
+pub fn game_seed() -> String {
+    with_default_game(MemoryGame::seed).unwrap_or_default()
+}
+
+// Difficulty name of the default game ("easy", "normal", "hard" or "expert")
+#[wasm_bindgen]
+pub fn game_difficulty() -> String {
+    with_default_game(MemoryGame::difficulty).unwrap_or_default()
+}
+
+// Change the default game's difficulty and deal a new deck (not while a game is running)
+#[wasm_bindgen]
+pub fn set_difficulty(name: &str) -> Result<(), JsValue> {
+    with_default_game(|game| game.set_difficulty(name)).unwrap_or_else(|| Err("The game is not initialized".into()))
+}
+
+fn set_game_difficulty(game: &Rc<GameInstance>, name: &str) -> Result<(), JsValue> {
+    let difficulty = Difficulty::from_name(name).ok_or_else(|| format!("Unknown difficulty: {}", name))?;
+
+    if replay::is_replaying(game) {
+        return Err("Can't change the difficulty during a replay".into());
+    }
+    if game.state.borrow().game.is_active() {
+        return Err("Can't change the difficulty during a game".into());
+    }
+
+    game.state.borrow_mut().difficulty = difficulty;
+
+    // Keep the selector in sync when called from JavaScript
+    game.view.difficulty_select.set_value(difficulty.name());
+
+    // New board for the new difficulty
+    prepare_cards(game);
+    update_game_stats(game);
+
+    Ok(())
+}
+
+// Card size and gap in pixels so the difficulty's grid fits the board
+fn card_layout(difficulty: Difficulty) -> (u32, u32) {
+    let (columns, rows) = difficulty.grid();
+    let gap = if rows > 3 { 12 } else { 20 };
+    let width = (BOARD_WIDTH - gap * (columns - 1)) / columns;
+    let height = (BOARD_HEIGHT - gap * (rows - 1)) / rows;
+    (width.min(height).min(MAX_CARD_SIZE), gap)
+}
+
+// Background for a face-up card: boards with more pairs than images reuse them with a tint
+fn card_face_style(card_value: u32) -> String {
+    let image = card_value % CARD_IMAGE_COUNT;
+    let hue = (card_value / CARD_IMAGE_COUNT) * 120;
+    format!("background-image: url('{}card-{}.png'); filter: hue-rotate({}deg);", IMAGE_PATH, image, hue)
+}
+
+// Apply a revealed session: check the server seed and difficulty against its commitment and
+// re-deal the deck
+fn apply_deck_session(game: &Rc<GameInstance>, session_id: &str, commitment_hex: &str, nonce_hex: &str, server_seed_hex: &str, client_seed: u64, difficulty: Difficulty) -> Result<(), JsValue> {
+    let commitment = hex::decode_array::<32>(commitment_hex).ok_or("Invalid session commitment")?;
+    let nonce = hex::decode_array::<32>(nonce_hex).ok_or("Invalid session nonce")?;
+    let server_seed = hex::decode_array::<32>(server_seed_hex).ok_or("Invalid server seed")?;
+
+    let (current_client_seed, game_started) = {
+        let game_state = game.state.borrow();
+        (game_state.client_seed, game_state.game.phase() != Phase::Idle)
+    };
+
+    // The deck was reset after this session was requested
+    if client_seed != current_client_seed || replay::is_replaying(game) {
+        return Err("Session belongs to a previous deck".into());
+    }
+
+    // Never re-deal a deck that is already being played
+    if game_started {
+        return Err("Game already started with the local deck".into());
+    }
+
+    if !commitment::verify_reveal(&commitment, &server_seed, difficulty) {
+        return Err("Server seed does not match the session commitment".into());
+    }
+
+    game.state.borrow_mut().session = Some(DeckSession {
+        id: session_id.to_string(),
+        commitment,
+        nonce,
     });
-    
+
+    deal_cards(game, commitment::deck_seed(&server_seed, client_seed));
+
+    web_sys::console::log_1(&format!("Deck session {} ready (commitment 0x{})", session_id, hex::encode(&commitment)).into());
+
+    Ok(())
+}
+
+fn start_game(game: &Rc<GameInstance>) {
+    let window = web_sys::window().expect("No global window");
+
+    // The board is showing a replay
+    if replay::is_replaying(game) {
+        return;
+    }
+
+    // A finished deck was seen, so it can't be played (or proven) again: deal a new one from a
+    // new session
+    if game.state.borrow().game.is_over() {
+        reset_game(game);
+        return;
+    }
+
+    // Start the game on the dealt deck
+    let (events, time_limit) = {
+        let mut game_state = game.state.borrow_mut();
+        (game_state.game.apply(Command::Start), game_state.game.difficulty().time_limit())
+    };
+    let Ok(events) = events else {
+        return;
+    };
+
     // Start the timer
-    setup_timer();
-    
+    setup_timer(game);
+
     // Update the UI
-    update_game_stats(&document);
-    
+    update_game_stats(game);
+
     // Disable the start button
-    if let Some(start_button) = document.get_element_by_id("start-game") {
-        start_button.set_attribute("disabled", "true").ok();
-        start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #95a5a6; color: white; border: none; border-radius: 5px; cursor: not-allowed;").ok();
-    }
-    
+    game.view.start_button.set_attribute("disabled", "true").ok();
+    game.view.start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #95a5a6; color: white; border: none; border-radius: 5px; cursor: not-allowed;").ok();
+
     // Disable the prove button
-    if let Some(prove_button) = document.get_element_by_id("prove-game") {
-        prove_button.set_attribute("disabled", "true").ok();
-        prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: not-allowed; opacity: 0.6;").ok();
-    }
-    
+    game.view.prove_button.set_attribute("disabled", "true").ok();
+    game.view.prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: not-allowed; opacity: 0.6;").ok();
+
     // Update card visuals (based on game started status)
-    update_card_visuals(&document);
-    
+    update_card_visuals(game);
+
+    for event in &events {
+        publish_engine_event(game, event);
+    }
+
     // Show notification
-    window.alert_with_message(&format!("Game started! Try to match all cards within {} seconds. Good luck!", TIME_LIMIT)).ok();
+    window.alert_with_message(&format!("Game started! Try to match all cards within {} seconds. Good luck!", time_limit)).ok();
 }
 
-fn setup_timer() {
+fn setup_timer(game: &Rc<GameInstance>) {
     let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        
-        // If a timer is already running, clear it
-        if let Some(interval_id) = game_state.timer_interval_id {
-            window.clear_interval_with_handle(interval_id);
-            game_state.timer_interval_id = None;
-        }
-        
-        // Start a new timer
-        let timer_callback = Closure::wrap(Box::new(move || {
-            let window = web_sys::window().expect("No global window");
-            let document = window.document().expect("No global document");
-            
-            let (game_active, time_up) = GAME_STATE.with(|state| {
-                let mut game_state = state.borrow_mut();
-                if game_state.game_started && !game_state.game_over {
-                    game_state.timer += 1;
-                    
-                    // Check if time is up
-                    if game_state.timer >= TIME_LIMIT {
-                        game_state.game_over = true;
-                        return (false, true);
-                    }
-                    
-                    return (true, false);
-                }
-                (false, false)
-            });
-            
-            if game_active {
-                // Update the timer
-                if let Some(timer_element) = document.get_element_by_id("timer") {
-                    let (timer, time_remaining) = GAME_STATE.with(|state| {
-                        let game_state = state.borrow();
-                        (game_state.timer, TIME_LIMIT - game_state.timer)
-                    });
-                    
+
+    // If a timer is already running, clear it
+    game.timer.take();
+
+    // Start a new timer
+    let timer_callback = listener(game, |game| {
+        let events = game.state.borrow_mut().game.apply(Command::Tick).unwrap_or_default();
+
+        for event in events {
+            publish_engine_event(game, &event);
+            match event {
+                Event::Tick { elapsed, remaining } => {
                     // Change color based on remaining time
-                    let timer_color = if time_remaining <= 10 {
+                    let timer_color = if remaining <= 10 {
                         "color: #e74c3c;" // Red (almost out of time)
-                    } else if time_remaining <= 30 {
+                    } else if remaining <= 30 {
                         "color: #f39c12;" // Orange (warning)
                     } else {
                         "color: white;" // Normal
                     };
-                    
-                    timer_element.set_attribute("style", &format!("font-size: 24px; {}", timer_color)).ok();
-                    timer_element.set_text_content(Some(&format!("Time: {} sec (Remaining: {})", timer, time_remaining)));
+
+                    // Update the timer
+                    game.view.timer.set_attribute("style", &format!("font-size: 24px; {}", timer_color)).ok();
+                    game.view.timer.set_text_content(Some(&format!("Time: {} sec (Remaining: {})", elapsed, remaining)));
                 }
+                // If time is up, end the game
+                Event::Lost => end_game(game, false), // Lost due to time running out
+                _ => {}
             }
-            
-            // If time is up, end the game
-            if time_up {
-                end_game(false); // Lost due to time running out
-            }
-            
-        }) as Box<dyn FnMut()>);
-        
-        let interval_id = window
-            .set_interval_with_callback_and_timeout_and_arguments_0(
-                timer_callback.as_ref().unchecked_ref(),
-                1000,
-            )
-            .expect("Could not create interval");
-        
-        game_state.timer_interval_id = Some(interval_id);
-        timer_callback.forget();
+        }
     });
+
+    let interval_id = window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            timer_callback.as_ref().unchecked_ref(),
+            1000,
+        )
+        .expect("Could not create interval");
+
+    game.timer.replace(Some(Interval { id: interval_id, _callback: timer_callback }));
+}
+
+// Wrap `handler` for a DOM callback. It only holds a weak reference, so the game can be dropped
+// while the browser still has the callback; it does nothing once the game is gone.
+fn listener(game: &Rc<GameInstance>, handler: impl Fn(&Rc<GameInstance>) + 'static) -> Listener {
+    let game = Rc::downgrade(game);
+    Closure::wrap(Box::new(move || {
+        if let Some(game) = game.upgrade() {
+            handler(&game);
+        }
+    }) as Box<dyn FnMut()>)
+}
+
+// Set `listener` as the click handler of `element`
+fn set_onclick(element: &Element, listener: &Listener) {
+    element
+        .dyn_ref::<HtmlElement>()
+        .expect("Not an HtmlElement")
+        .set_onclick(Some(listener.as_ref().unchecked_ref()));
 }
 
-fn create_game_board(document: &Document) -> Result<(), JsValue> {
+fn create_game_board(document: &Document, difficulty: Difficulty) -> Result<GameView, JsValue> {
     // Create game area container
-    let container = document.create_element("div")?;
-    container.set_id("game-container");
-    container.set_attribute("style", &format!("width: 1024px; height: 768px; background-image: url('{}background.gif'); background-size: cover; position: relative; margin: 0 auto;", IMAGE_PATH))?;
-    
+    let root = document.create_element("div")?;
+    root.set_class_name("memory-game");
+    root.set_attribute("style", &format!("width: 1024px; height: 768px; background-image: url('{}background.gif'); background-size: cover; position: relative; margin: 0 auto;", IMAGE_PATH))?;
+
     // Top area - Score and timer
     let header = document.create_element("div")?;
-    header.set_id("game-header");
+    header.set_class_name("game-header");
     header.set_attribute("style", "height: 150px; display: flex; justify-content: space-between; align-items: center; padding: 0 50px;")?;
-    
+
     let timer = document.create_element("div")?;
-    timer.set_id("timer");
+    timer.set_class_name("timer");
     timer.set_attribute("style", "font-size: 24px; color: white;")?;
-    timer.set_text_content(Some(&format!("Time: 0 sec (Remaining: {})", TIME_LIMIT)));
-    
+    timer.set_text_content(Some(&format!("Time: 0 sec (Remaining: {})", difficulty.time_limit())));
+
     let moves = document.create_element("div")?;
-    moves.set_id("moves");
+    moves.set_class_name("moves");
     moves.set_attribute("style", "font-size: 24px; color: white;")?;
     moves.set_text_content(Some("Moves: 0"));
-    
+
     let score = document.create_element("div")?;
-    score.set_id("score");
+    score.set_class_name("score");
     score.set_attribute("style", "font-size: 24px; color: white;")?;
     score.set_text_content(Some("Score: 0"));
-    
+
     header.append_child(&timer)?;
     header.append_child(&moves)?;
     header.append_child(&score)?;
-    
+
     // Middle area - Game cards
     let board = document.create_element("div")?;
-    board.set_id("game-board");
-    board.set_attribute("style", "height: 480px; display: flex; flex-wrap: wrap; justify-content: center; align-items: center; gap: 20px; padding: 20px;")?;
-    
-    // Bottom area - Control buttons
+    board.set_class_name("game-board");
+    board.set_attribute("style", BOARD_STYLE)?;
+
+    // Bottom area - Control buttons (attach_controls adds their handlers)
     let footer = document.create_element("div")?;
-    footer.set_id("game-footer");
-    footer.set_attribute("style", "height: 138px; display: flex; justify-content: center; align-items: center; gap: 30px;")?;
-    
+    footer.set_class_name("game-footer");
+    footer.set_attribute("style", FOOTER_STYLE)?;
+
     let start_button = document.create_element("button")?;
-    start_button.set_id("start-game");
+    start_button.set_class_name("start-game");
     start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #2ecc71; color: white; border: none; border-radius: 5px; cursor: pointer;")?;
     start_button.set_text_content(Some("Start Game"));
-    
-    // Add click event to start button
-    let start_closure = Closure::wrap(Box::new(move || {
-        start_game();
-        play_sound("game-start.mp3");
-    }) as Box<dyn FnMut()>);
-    
-    start_button
-        .dyn_ref::<HtmlElement>()
-        .expect("Not an HtmlElement")
-        .set_onclick(Some(start_closure.as_ref().unchecked_ref()));
-    
-    start_closure.forget();
-    
+
     let reset_button = document.create_element("button")?;
-    reset_button.set_id("reset-game");
+    reset_button.set_class_name("reset-game");
     reset_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #e74c3c; color: white; border: none; border-radius: 5px; cursor: pointer;")?;
     reset_button.set_text_content(Some("Reset Game"));
-    
-    // Add click event to reset button
-    let reset_closure = Closure::wrap(Box::new(move || {
-        reset_game();
-        play_sound("button-click.mp3");
-    }) as Box<dyn FnMut()>);
-    
-    reset_button
-        .dyn_ref::<HtmlElement>()
-        .expect("Not an HtmlElement")
-        .set_onclick(Some(reset_closure.as_ref().unchecked_ref()));
-    
-    reset_closure.forget();
-    
+
     // Prove button
     let prove_button = document.create_element("button")?;
-    prove_button.set_id("prove-game");
+    prove_button.set_class_name("prove-game");
     prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: not-allowed; opacity: 0.6;")?;
     prove_button.set_text_content(Some("Prove (SP1)"));
     prove_button.set_attribute("disabled", "true")?;
-    
-    // Add click event to prove button
-    let prove_closure = Closure::wrap(Box::new(move || {
-        start_sp1_proof();
-        play_sound("button-click.mp3");
-    }) as Box<dyn FnMut()>);
-    
-    prove_button
-        .dyn_ref::<HtmlElement>()
-        .expect("Not an HtmlElement")
-        .set_onclick(Some(prove_closure.as_ref().unchecked_ref()));
-    
-    prove_closure.forget();
-    
+
+    // Difficulty selector
+    let difficulty_select = document.create_element("select")?.dyn_into::<HtmlSelectElement>()?;
+    difficulty_select.set_class_name("difficulty-select");
+    difficulty_select.set_attribute("style", "padding: 15px; font-size: 20px; background-color: #440e43; color: white; border: none; border-radius: 5px; cursor: pointer;")?;
+
+    for option_difficulty in Difficulty::ALL {
+        let option = document.create_element("option")?;
+        option.set_attribute("value", option_difficulty.name())?;
+        option.set_text_content(Some(&format!("{:?} ({} pairs, {}s)", option_difficulty, option_difficulty.pairs(), option_difficulty.time_limit())));
+        if option_difficulty == difficulty {
+            option.set_attribute("selected", "true")?;
+        }
+        difficulty_select.append_child(&option)?;
+    }
+
+    footer.append_child(&difficulty_select)?;
     footer.append_child(&start_button)?;
     footer.append_child(&reset_button)?;
     footer.append_child(&prove_button)?;
-    
+
     // Add all areas to the main container
-    container.append_child(&header)?;
-    container.append_child(&board)?;
-    container.append_child(&footer)?;
-    
-    // Add container to the body
-    document.body().unwrap().append_child(&container)?;
-    
-    Ok(())
+    root.append_child(&header)?;
+    root.append_child(&board)?;
+    root.append_child(&footer)?;
+
+    Ok(GameView {
+        root,
+        timer,
+        moves,
+        score,
+        board,
+        footer,
+        start_button,
+        reset_button,
+        prove_button,
+        difficulty_select,
+    })
 }
 
-// Reset the game
-fn reset_game() {
-    let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    // Stop the timer
-    GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        if let Some(interval_id) = game_state.timer_interval_id {
-            window.clear_interval_with_handle(interval_id);
-            game_state.timer_interval_id = None;
+// Add the click and change handlers of the control buttons and difficulty selector
+fn attach_controls(game: &Rc<GameInstance>) {
+    let start_listener = listener(game, |game| {
+        start_game(game);
+        game.play_sound("game-start.mp3");
+    });
+    set_onclick(&game.view.start_button, &start_listener);
+
+    let reset_listener = listener(game, |game| {
+        reset_game(game);
+        game.play_sound("button-click.mp3");
+    });
+    set_onclick(&game.view.reset_button, &reset_listener);
+
+    let prove_listener = listener(game, |game| {
+        start_sp1_proof(game);
+        game.play_sound("button-click.mp3");
+    });
+    set_onclick(&game.view.prove_button, &prove_listener);
+
+    let difficulty_listener = listener(game, |game| {
+        let window = web_sys::window().expect("No global window");
+        let select = &game.view.difficulty_select;
+        if let Err(error) = set_game_difficulty(game, &select.value()) {
+            window.alert_with_message(&error.as_string().unwrap_or_default()).ok();
+            select.set_value(game.state.borrow().difficulty.name());
         }
-        game_state.game_started = false;
-        game_state.game_over = false;
+        game.play_sound("button-click.mp3");
     });
-    
-    // Enable the start button
-    if let Some(start_button) = document.get_element_by_id("start-game") {
-        start_button.remove_attribute("disabled").ok();
-        start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #2ecc71; color: white; border: none; border-radius: 5px; cursor: pointer;").ok();
+    game.view.difficulty_select.set_onchange(Some(difficulty_listener.as_ref().unchecked_ref()));
+
+    game.controls.replace(vec![start_listener, reset_listener, prove_listener, difficulty_listener]);
+}
+
+// Reset the game
+fn reset_game(game: &Rc<GameInstance>) {
+    leave_game(game);
+
+    // Re-prepare the cards (a new deck is a new, unstarted game)
+    prepare_cards(game);
+
+    update_game_stats(game);
+}
+
+// Stop the current game or replay and restore the controls; the caller deals the next deck
+fn leave_game(game: &Rc<GameInstance>) {
+    // Leaving a replay deals a new deck like a reset, but the replay isn't part of the transcript
+    if replay::is_replaying(game) {
+        replay::stop_replay(game);
+    } else {
+        record(game, Action::Reset);
     }
-    
+
+    // Stop the timer and any pending match check
+    game.timer.take();
+    game.pending_check.take();
+
+    // Enable the start button
+    game.view.start_button.remove_attribute("disabled").ok();
+    game.view.start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #2ecc71; color: white; border: none; border-radius: 5px; cursor: pointer;").ok();
+    game.view.start_button.set_text_content(Some("Start Game"));
+
     // Reset the timer color
-    if let Some(timer_element) = document.get_element_by_id("timer") {
-        timer_element.set_attribute("style", "font-size: 24px; color: white;").ok();
-    }
-    
+    game.view.timer.set_attribute("style", "font-size: 24px; color: white;").ok();
+
     // Disable the prove button
-    if let Some(prove_button) = document.get_element_by_id("prove-game") {
-        prove_button.set_attribute("disabled", "true").ok();
-        prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: not-allowed; opacity: 0.6;").ok();
-    }
-    
-    // Re-prepare the cards
-    prepare_cards(&document);
-    
-    // Reset statistics
-    GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        game_state.moves = 0;
-        game_state.timer = 0;
-        game_state.score = 0;
-        game_state.flipped_cards.clear();
-        game_state.matched_pairs.clear();
-        game_state.is_checking = false;
-    });
-    
-    update_game_stats(&document);
+    game.view.prove_button.set_attribute("disabled", "true").ok();
+    game.view.prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: not-allowed; opacity: 0.6;").ok();
 }
 
-fn render_game_board(document: &Document) {
-    if let Some(board) = document.get_element_by_id("game-board") {
-        // First, clear existing cards
-        while let Some(child) = board.first_child() {
-            board.remove_child(&child).expect("Failed to remove child element");
-        }
-        
-        // Create cards - defining variables outside GAME_STATE
-        let cards: Vec<usize> = GAME_STATE.with(|state| {
-            let game_state = state.borrow();
-            game_state.cards.clone()
-        });
-        
-        for (index, &card_value) in cards.iter().enumerate() {
-            let card_element = document.create_element("div").expect("Failed to create element");
-            card_element.set_attribute("data-index", &index.to_string()).expect("Failed to set attribute");
-            card_element.set_attribute("data-value", &card_value.to_string()).expect("Failed to set attribute");
-            
-            // Set card style (back face)
-            card_element.set_attribute(
-                "style", 
-                &format!("width: 120px; height: 120px; background-image: url('{}card-back.png'); background-size: cover; cursor: pointer; transform-style: preserve-3d; transition: transform 0.5s; border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,0.1);", IMAGE_PATH)
-            ).expect("Failed to set style");
-            
-            // Add click event to the card
-            let click_index = index.clone();
-            let closure = Closure::wrap(Box::new(move || {
-                card_click(click_index);
-            }) as Box<dyn FnMut()>);
-            
-            card_element
-                .dyn_ref::<HtmlElement>()
-                .expect("Not an HtmlElement")
-                .set_onclick(Some(closure.as_ref().unchecked_ref()));
-            
-            closure.forget();
-            
-            board.append_child(&card_element).expect("Failed to add card");
-        }
+// Remove the cards or proof panel from the board, releasing their handlers
+fn clear_board(game: &GameInstance) {
+    let board = &game.view.board;
+    while let Some(child) = board.first_child() {
+        board.remove_child(&child).expect("Failed to remove child element");
     }
+    game.cards.borrow_mut().clear();
+    game.proof_panel.take();
 }
 
-fn card_click(index: usize) {
+fn render_game_board(game: &Rc<GameInstance>) {
     let window = web_sys::window().expect("No global window");
     let document = window.document().expect("No global document");
-    
-    // If the game hasn't started, has ended, or another operation is in progress, don't process the click
-    let (game_started, game_over, is_checking, card_already_handled) = GAME_STATE.with(|state| {
-        let game_state = state.borrow();
-        
-        // Card is already flipped or matched
-        let card_value = game_state.cards[index];
-        let already_handled = game_state.flipped_cards.contains(&index) || 
-                              game_state.matched_pairs.contains(&card_value);
-                              
-        (game_state.game_started, game_state.game_over, game_state.is_checking, already_handled)
-    });
-    
-    if !game_started || game_over || is_checking || card_already_handled {
+    let board = &game.view.board;
+
+    // First, clear existing cards
+    clear_board(game);
+
+    let (cards, difficulty): (Vec<u32>, Difficulty) = {
+        let game_state = game.state.borrow();
+        (game_state.game.cards().to_vec(), game_state.game.difficulty())
+    };
+
+    // Lay the cards out on the difficulty's grid
+    let (columns, _) = difficulty.grid();
+    let (card_size, gap) = card_layout(difficulty);
+    board.set_attribute("style", &format!("height: 480px; display: grid; grid-template-columns: repeat({}, {}px); justify-content: center; align-content: center; gap: {}px; padding: 20px;", columns, card_size, gap)).expect("Failed to set style");
+
+    let mut rendered = Vec::with_capacity(cards.len());
+    for (index, &card_value) in cards.iter().enumerate() {
+        let card_element = document.create_element("div").expect("Failed to create element");
+        card_element.set_attribute("data-index", &index.to_string()).expect("Failed to set attribute");
+        card_element.set_attribute("data-value", &card_value.to_string()).expect("Failed to set attribute");
+
+        // Set card style (back face)
+        card_element.set_attribute(
+            "style",
+            &format!("width: {}px; height: {}px; background-image: url('{}card-back.png'); background-size: cover; cursor: pointer; transform-style: preserve-3d; transition: transform 0.5s; border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,0.1);", card_size, card_size, IMAGE_PATH)
+        ).expect("Failed to set style");
+
+        // Add click event to the card
+        let on_click = listener(game, move |game| card_click(game, index));
+        set_onclick(&card_element, &on_click);
+
+        board.append_child(&card_element).expect("Failed to add card");
+        rendered.push(Card { element: card_element, _on_click: on_click });
+    }
+    game.cards.replace(rendered);
+}
+
+fn card_click(game: &Rc<GameInstance>, index: usize) {
+    let window = web_sys::window().expect("No global window");
+
+    // Replays are only watched
+    if replay::is_replaying(game) {
         return;
     }
-    
-    // Flip the card and handle game logic
-    let should_check = GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        
-        // Flip the card
-        game_state.flipped_cards.push(index);
-        
-        // If two cards are flipped, return true for checking
-        game_state.flipped_cards.len() == 2
-    });
-    
+
+    // The engine refuses clicks before the game starts, after it ends, while a pair is being
+    // checked, and on cards that are already face up or matched
+    let (events, should_check) = {
+        let mut game_state = game.state.borrow_mut();
+        let events = game_state.game.apply(Command::Flip(index));
+
+        // If two cards are flipped, they need to be checked
+        (events, game_state.game.phase() == Phase::Checking)
+    };
+
+    let Ok(events) = events else {
+        return;
+    };
+
     // Card flip sound
-    play_sound("card-flip.mp3");
-    
+    game.play_sound("card-flip.mp3");
+
     // Update card visuals
-    update_card_visuals(&document);
-    
+    update_card_visuals(game);
+
+    for event in &events {
+        publish_engine_event(game, event);
+    }
+
     // If two cards are flipped, check for a match
     if should_check {
-        // Update game state
-        GAME_STATE.with(|state| {
-            let mut game_state = state.borrow_mut();
-            game_state.moves += 1;
-            game_state.is_checking = true;
-        });
-        
-        // Update statistics
-        update_game_stats(&document);
-        
+        // Update statistics (the move count changed)
+        update_game_stats(game);
+
         // Check for a match (after 1000ms delay)
-        let check_closure = Closure::wrap(Box::new(move || {
-            check_match();
-        }) as Box<dyn FnMut()>);
-        
-        window.set_timeout_with_callback_and_timeout_and_arguments_0(
-            check_closure.as_ref().unchecked_ref(),
+        let check_callback = listener(game, check_match);
+
+        let timeout_id = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+            check_callback.as_ref().unchecked_ref(),
             1000 // 1000ms delay (increased for better visibility)
         ).expect("setTimeout failed");
-        
-        check_closure.forget();
+
+        game.pending_check.replace(Some(Timeout { id: timeout_id, _callback: check_callback }));
     }
 }
 
-fn check_match() {
-    let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    let (is_match, game_completed) = GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        
-        if game_state.flipped_cards.len() != 2 {
-            game_state.is_checking = false;
-            return (false, false);
-        }
-        
-        let first_card_index = game_state.flipped_cards[0];
-        let second_card_index = game_state.flipped_cards[1];
-        
-        let first_card_value = game_state.cards[first_card_index];
-        let second_card_value = game_state.cards[second_card_index];
-        
-        // Do the cards match?
-        if first_card_value == second_card_value {
-            // Save the matched pair
-            game_state.matched_pairs.push(first_card_value);
-            
-            
-            // Check if all cards are matched
-            let all_matched = game_state.matched_pairs.len() == 8; // 8 pairs of cards
-            
-            // Clear flipped cards
-            game_state.flipped_cards.clear();
-            game_state.is_checking = false;
-            
-            (true, all_matched)
-        } else {
-            // No match, flip cards back
-            game_state.flipped_cards.clear();
-            game_state.is_checking = false;
-            
-            (false, false)
-        }
-    });
-    
+fn check_match(game: &Rc<GameInstance>) {
+    // Nothing to check if the game ended during the delay
+    let Ok(events) = game.state.borrow_mut().game.apply(Command::Resolve) else {
+        return;
+    };
+
     // Update card visuals
-    update_card_visuals(&document);
-    
+    update_card_visuals(game);
+
     // Update statistics (score may have changed)
-    update_game_stats(&document);
-    
-    // Play match sound
-    if is_match {
-        play_sound("match.mp3");
-    } else {
-        play_sound("no-match.mp3");
-    }
-    
-    // If game completed
-    if game_completed {
-        end_game(true); // Won
+    update_game_stats(game);
+
+    for event in events {
+        publish_engine_event(game, &event);
+        match event {
+            // Play match sound
+            Event::MatchFound { .. } => game.play_sound("match.mp3"),
+            Event::Mismatch { .. } => game.play_sound("no-match.mp3"),
+            // If game completed
+            Event::Won { .. } => end_game(game, true), // Won
+            _ => {}
+        }
     }
 }
 
-fn update_card_visuals(document: &Document) {
-    // Get required data from GAME_STATE
-    let (cards, flipped_cards, matched_pairs, game_started, game_over) = GAME_STATE.with(|state| {
-        let game_state = state.borrow();
+fn update_card_visuals(game: &GameInstance) {
+    // Get required data from the game state
+    let (cards, face_up, matched, game_active, difficulty) = {
+        let game = &game.state.borrow().game;
+        let cards = game.cards().to_vec();
         (
-            game_state.cards.clone(),
-            game_state.flipped_cards.clone(),
-            game_state.matched_pairs.clone(),
-            game_state.game_started,
-            game_state.game_over
+            cards.clone(),
+            (0..cards.len()).map(|index| game.is_face_up(index)).collect::<Vec<_>>(),
+            (0..cards.len()).map(|index| game.is_matched(index)).collect::<Vec<_>>(),
+            game.is_active(),
+            game.difficulty()
         )
-    });
-    
+    };
+    let (card_size, _) = card_layout(difficulty);
+
     // Loop through all cards
-    for index in 0..cards.len() {
-        if let Some(card_element) = document.query_selector(&format!("[data-index=\"{}\"]", index)).expect("Query failed") {
-            let card_value = cards[index];
-            
-            // Check if card is flipped or matched
-            if matched_pairs.contains(&card_value) {
-                // Matched card - faded look
-                card_element.set_attribute(
-                    "style", 
-                    &format!("width: 120px; height: 120px; background-image: url('{}card-{}.png'); background-size: cover; cursor: default; opacity: 0.7; transform: rotateY(0deg); border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);", IMAGE_PATH, card_value)
-                ).expect("Failed to set style");
-            } else if flipped_cards.contains(&index) {
-                // Flipped but not yet matched card
-                card_element.set_attribute(
-                    "style", 
-                    &format!("width: 120px; height: 120px; background-image: url('{}card-{}.png'); background-size: cover; cursor: pointer; transform: rotateY(0deg); border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,0.2);", IMAGE_PATH, card_value)
-                ).expect("Failed to set style");
-            } else {
-                // Face down card
-                let cursor_style = if game_started && !game_over { "cursor: pointer;" } else { "cursor: not-allowed;" };
-                card_element.set_attribute(
-                    "style", 
-                    &format!("width: 120px; height: 120px; background-image: url('{}card-back.png'); background-size: cover; {} transform: rotateY(0deg); border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,0.1);", IMAGE_PATH, cursor_style)
-                ).expect("Failed to set style");
-            }
+    for (index, card) in game.cards.borrow().iter().enumerate() {
+        let (Some(&card_value), Some(&is_matched), Some(&is_face_up)) = (cards.get(index), matched.get(index), face_up.get(index)) else {
+            break;
+        };
+        let card_element = &card.element;
+
+        // Check if card is flipped or matched
+        if is_matched {
+            // Matched card - faded look
+            card_element.set_attribute(
+                "style",
+                &format!("width: {}px; height: {}px; {} background-size: cover; cursor: default; opacity: 0.7; transform: rotateY(0deg); border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);", card_size, card_size, card_face_style(card_value))
+            ).expect("Failed to set style");
+        } else if is_face_up {
+            // Flipped but not yet matched card
+            card_element.set_attribute(
+                "style",
+                &format!("width: {}px; height: {}px; {} background-size: cover; cursor: pointer; transform: rotateY(0deg); border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,0.2);", card_size, card_size, card_face_style(card_value))
+            ).expect("Failed to set style");
+        } else {
+            // Face down card
+            let cursor_style = if game_active { "cursor: pointer;" } else { "cursor: not-allowed;" };
+            card_element.set_attribute(
+                "style",
+                &format!("width: {}px; height: {}px; background-image: url('{}card-back.png'); background-size: cover; {} transform: rotateY(0deg); border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,0.1);", card_size, card_size, IMAGE_PATH, cursor_style)
+            ).expect("Failed to set style");
         }
     }
 }
 
-fn update_game_stats(document: &Document) {
-    let (moves, timer, score, time_remaining) = GAME_STATE.with(|state| {
-        let game_state = state.borrow();
+fn update_game_stats(game: &GameInstance) {
+    let (moves, timer, score, time_remaining) = {
+        let game = &game.state.borrow().game;
         (
-            game_state.moves, 
-            game_state.timer, 
-            game_state.score,
-            if game_state.timer < TIME_LIMIT { TIME_LIMIT - game_state.timer } else { 0 }
+            game.moves(),
+            game.elapsed(),
+            game.score(),
+            game.remaining_time()
         )
-    });
-    
+    };
+
     // Update move count
-    if let Some(moves_element) = document.get_element_by_id("moves") {
-        moves_element.set_text_content(Some(&format!("Moves: {}", moves)));
-    }
-    
+    game.view.moves.set_text_content(Some(&format!("Moves: {}", moves)));
+
     // Update timer
-    if let Some(timer_element) = document.get_element_by_id("timer") {
-        timer_element.set_text_content(Some(&format!("Time: {} sec (Remaining: {})", timer, time_remaining)));
-    }
-    
+    game.view.timer.set_text_content(Some(&format!("Time: {} sec (Remaining: {})", timer, time_remaining)));
+
     // Update score
-    if let Some(score_element) = document.get_element_by_id("score") {
-        score_element.set_text_content(Some(&format!("Score: {}", score)));
-    }
+    game.view.score.set_text_content(Some(&format!("Score: {}", score)));
 }
 
 // End the game (won or time's up)
-fn end_game(is_winner: bool) {
+fn end_game(game: &GameInstance, is_winner: bool) {
     let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
+
     // Stop the timer
-    GAME_STATE.with(|state| {
-        let mut game_state = state.borrow_mut();
-        if let Some(interval_id) = game_state.timer_interval_id {
-            window.clear_interval_with_handle(interval_id);
-            game_state.timer_interval_id = None;
-        }
-        game_state.game_started = false;
-        game_state.game_over = true;
-    });
-    
-// Get statistics
-let (moves, timer, matched_pairs) = GAME_STATE.with(|state| {
-    let game_state = state.borrow();
-    (game_state.moves, game_state.timer, game_state.matched_pairs.len())
-});
-
-// Yeni puanlama sistemi: Kalan Zaman - Hamle Sayısı
-let remaining_time = if timer < TIME_LIMIT { TIME_LIMIT - timer } else { 0 };
-let score = if is_winner { 
-    // Kazandıysa: Kalan Zaman - Hamle Sayısı (negatif olabilir)
-    remaining_time as i32 - moves as i32 
-} else { 
-    0 // Kaybettiyse 0 puan
-};
-
-// Skoru güncelle
-GAME_STATE.with(|state| {
-    let mut game_state = state.borrow_mut();
-    game_state.score = if score < 0 { 0 } else { score as usize }; // Negatif puanları 0 olarak kabul et
-});
-    
-// Create message
-let message = if is_winner {
-    format!(
-        "Congratulations! You won the game!\nMoves: {}\nScore: {} (Remaining Time - Moves)\nTime: {} seconds",
-        moves, score, timer
-    )
-} else {
-    format!(
-        "Time's up! Game over.\nMoves: {}\nScore: {}\nTime: {} seconds",
-        moves, score, timer
-    )
-};
-    
+    game.timer.take();
+
+    // Get statistics (the engine already scored the game: Remaining Time - Moves)
+    let (moves, timer, score) = {
+        let game = &game.state.borrow().game;
+        (game.moves(), game.elapsed(), game.score())
+    };
+
+    // Create message
+    let message = if is_winner {
+        format!(
+            "Congratulations! You won the game!\nMoves: {}\nScore: {} (Remaining Time - Moves)\nTime: {} seconds",
+            moves, score, timer
+        )
+    } else {
+        format!(
+            "Time's up! Game over.\nMoves: {}\nScore: {}\nTime: {} seconds",
+            moves, score, timer
+        )
+    };
+
     // Show congratulations or notification message
     window.alert_with_message(&message).expect("Alert could not be shown");
-    
-    // Enable the start button
-    if let Some(start_button) = document.get_element_by_id("start-game") {
-        start_button.remove_attribute("disabled").ok();
-        start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #2ecc71; color: white; border: none; border-radius: 5px; cursor: pointer;").ok();
-    }
-    
+
+    // Enable the start button, which deals a new deck first
+    game.view.start_button.remove_attribute("disabled").ok();
+    game.view.start_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #2ecc71; color: white; border: none; border-radius: 5px; cursor: pointer;").ok();
+    game.view.start_button.set_text_content(Some("New Game"));
+
     // Enable the prove button
-    if let Some(prove_button) = document.get_element_by_id("prove-game") {
-        prove_button.remove_attribute("disabled").ok();
-        prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: pointer; opacity: 1.0;").ok();
-    }
-    
+    game.view.prove_button.remove_attribute("disabled").ok();
+    game.view.prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: pointer; opacity: 1.0;").ok();
+
     // Game ending sound
     if is_winner {
-        play_sound("success.mp3");
+        game.play_sound("success.mp3");
     } else {
-        play_sound("lose.mp3");
+        game.play_sound("lose.mp3");
     }
 }
 
 // Show proof in game area
-fn show_proof_in_game_area(document: &Document) -> Result<(), JsValue> {
-    // Get game board element
-    if let Some(board) = document.get_element_by_id("game-board") {
-        // First, clear existing content
-        while let Some(child) = board.first_child() {
-            board.remove_child(&child)?;
-        }
-        
-        // Create proof panel container
-        let proof_container = document.create_element("div")?;
-        proof_container.set_id("proof-container");
-        proof_container.set_attribute("style", "
-            width: 100%;
-            height: 100%;
-            background-color: rgba(0, 0, 0, 0.85);
-            color: #2ecc71;
-            font-family: monospace;
-            border-radius: 8px;
-            padding: 20px;
-            display: flex;
-            flex-direction: column;
-        ")?;
-        
-        // Create proof header
-        let proof_header = document.create_element("div")?;
-        proof_header.set_id("proof-header");
-        proof_header.set_text_content(Some("SP1 Zero Knowledge Proof Process"));
-        proof_header.set_attribute("style", "
+fn show_proof_in_game_area(game: &Rc<GameInstance>) -> Result<(), JsValue> {
+    let window = web_sys::window().expect("No global window");
+    let document = window.document().expect("No global document");
+    let board = &game.view.board;
+
+    // First, clear existing content
+    clear_board(game);
+
+    // Back to the plain layout (the card grid depends on the difficulty)
+    board.set_attribute("style", BOARD_STYLE)?;
+
+    // Create proof panel container (sp1-bridge.js adds its result card to this game's proof log,
+    // so nothing here has an id: several games can show their proofs at once)
+    let proof_container = document.create_element("div")?;
+    proof_container.set_class_name("proof-container");
+    proof_container.set_attribute("style", "
+        width: 100%;
+        height: 100%;
+        background-color: rgba(0, 0, 0, 0.85);
+        color: #2ecc71;
+        font-family: monospace;
+        border-radius: 8px;
+        padding: 20px;
+        display: flex;
+        flex-direction: column;
+    ")?;
+
+    // Create proof header
+    let proof_header = document.create_element("div")?;
+    proof_header.set_class_name("proof-header");
+    proof_header.set_text_content(Some("SP1 Zero Knowledge Proof Process"));
+    proof_header.set_attribute("style", "
+        font-size: 24px;
+        font-weight: bold;
+        text-align: center;
+        margin-bottom: 20px;
+        padding-bottom: 10px;
+        border-bottom: 1px solid #2ecc71;
+    ")?;
+
+    // Create proof log area
+    let proof_log = document.create_element("div")?;
+    proof_log.set_class_name("proof-log");
+    proof_log.set_attribute("style", "
+        flex: 1;
+        overflow-y: auto;
+        font-size: 14px;
+        padding: 10px;
+        background-color: rgba(0, 0, 0, 0.5);
+        border-radius: 4px;
+        white-space: pre-wrap;
+        line-height: 1.5;
+    ")?;
+
+    // Create proof buttons area
+    let proof_buttons = document.create_element("div")?;
+    proof_buttons.set_attribute("style", "
+        margin-top: 20px;
+        display: flex;
+        justify-content: center;
+        gap: 20px;
+    ")?;
+
+    // Back to Game button
+    let back_button = document.create_element("button")?;
+    back_button.set_text_content(Some("Back to Game"));
+    back_button.set_attribute("style", "
+        padding: 10px 20px;
+        background-color: #3498db;
+        color: white;
+        border: none;
+        border-radius: 5px;
+        cursor: pointer;
+        font-size: 16px;
+    ")?;
+
+    // Add click event to button
+    let back_listener = listener(game, |game| {
+        render_game_board(game);
+        game.play_sound("button-click.mp3");
+    });
+    set_onclick(&back_button, &back_listener);
+
+    // Restart Game button
+    let restart_button = document.create_element("button")?;
+    restart_button.set_text_content(Some("Restart Game"));
+    restart_button.set_attribute("style", "
+        padding: 10px 20px;
+        background-color: #e74c3c;
+        color: white;
+        border: none;
+        border-radius: 5px;
+        cursor: pointer;
+        font-size: 16px;
+    ")?;
+
+    // Add click event to button
+    let restart_listener = listener(game, |game| {
+        reset_game(game);
+        game.play_sound("button-click.mp3");
+    });
+    set_onclick(&restart_button, &restart_listener);
+
+    // Add buttons
+    proof_buttons.append_child(&back_button)?;
+    proof_buttons.append_child(&restart_button)?;
+
+    // Add elements to proof container
+    proof_container.append_child(&proof_header)?;
+    proof_container.append_child(&proof_log)?;
+    proof_container.append_child(&proof_buttons)?;
+
+    // Add proof container to game area
+    board.append_child(&proof_container)?;
+
+    game.proof_panel.replace(Some(ProofPanel {
+        header: proof_header,
+        log: proof_log,
+        _listeners: vec![back_listener, restart_listener],
+    }));
+
+    Ok(())
+}
+
+// Show a proof result in the header of the proof panel
+fn set_proof_header(game: &GameInstance, text: &str, color: &str) -> Result<(), JsValue> {
+    if let Some(panel) = game.proof_panel.borrow().as_ref() {
+        panel.header.set_text_content(Some(text));
+        panel.header.set_attribute("style", &format!("
             font-size: 24px;
             font-weight: bold;
             text-align: center;
             margin-bottom: 20px;
             padding-bottom: 10px;
-            border-bottom: 1px solid #2ecc71;
-        ")?;
-        
-        // Create proof log area
-        let proof_log = document.create_element("div")?;
-        proof_log.set_id("proof-log");
-        proof_log.set_attribute("style", "
-            flex: 1;
-            overflow-y: auto;
-            font-size: 14px;
-            padding: 10px;
-            background-color: rgba(0, 0, 0, 0.5);
-            border-radius: 4px;
-            white-space: pre-wrap;
-            line-height: 1.5;
-        ")?;
-        
-        // Create proof buttons area
-        let proof_buttons = document.create_element("div")?;
-        proof_buttons.set_id("proof-buttons");
-        proof_buttons.set_attribute("style", "
-            margin-top: 20px;
-            display: flex;
-            justify-content: center;
-            gap: 20px;
-        ")?;
-        
-        // Back to Game button
-        let back_button = document.create_element("button")?;
-        back_button.set_id("back-to-game");
-        back_button.set_text_content(Some("Back to Game"));
-        back_button.set_attribute("style", "
-            padding: 10px 20px;
-            background-color: #3498db;
-            color: white;
-            border: none;
-            border-radius: 5px;
-            cursor: pointer;
-            font-size: 16px;
-        ")?;
-        
-        // Add click event to button
-        let back_closure = Closure::wrap(Box::new(move || {
-            let window = web_sys::window().expect("No global window");
-            let document = window.document().expect("No global document");
-            render_game_board(&document);
-            play_sound("button-click.mp3");
-        }) as Box<dyn FnMut()>);
-        
-        back_button
-            .dyn_ref::<HtmlElement>()
-            .expect("Not an HtmlElement")
-            .set_onclick(Some(back_closure.as_ref().unchecked_ref()));
-        
-        back_closure.forget();
-        
-        // Restart Game button
-        let restart_button = document.create_element("button")?;
-        restart_button.set_id("restart-game");
-        restart_button.set_text_content(Some("Restart Game"));
-        restart_button.set_attribute("style", "
-            padding: 10px 20px;
-            background-color: #e74c3c;
-            color: white;
-            border: none;
-            border-radius: 5px;
-            cursor: pointer;
-            font-size: 16px;
-        ")?;
-        
-        // Add click event to button
-        let restart_closure = Closure::wrap(Box::new(move || {
-            reset_game();
-            play_sound("button-click.mp3");
-        }) as Box<dyn FnMut()>);
-        
-        restart_button
-            .dyn_ref::<HtmlElement>()
-            .expect("Not an HtmlElement")
-            .set_onclick(Some(restart_closure.as_ref().unchecked_ref()));
-        
-        restart_closure.forget();
-        
-        // Add buttons
-        proof_buttons.append_child(&back_button)?;
-        proof_buttons.append_child(&restart_button)?;
-        
-        // Add elements to proof container
-        proof_container.append_child(&proof_header)?;
-        proof_container.append_child(&proof_log)?;
-        proof_container.append_child(&proof_buttons)?;
-        
-        // Add proof container to game area
-        board.append_child(&proof_container)?;
+            border-bottom: 1px solid {};
+            color: {};
+        ", color, color))?;
     }
-    
+
     Ok(())
 }
 
-// Add log message to proof area
-#[wasm_bindgen]
-pub fn log_to_proof_area(message: &str) -> Result<(), JsValue> {
-    let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    if let Some(proof_log) = document.get_element_by_id("proof-log") {
-        // Add timestamp
-        let date = js_sys::Date::new_0();
-        let timestamp = format!("[{:02}:{:02}:{:02}] ", 
-            date.get_hours(), 
-            date.get_minutes(), 
-            date.get_seconds()
-        );
-        
-        // Create new line
-        let line = document.create_element("div")?;
-        line.set_text_content(Some(&format!("{}{}", timestamp, message)));
-        
-        // Set line color
-        if message.contains("error") || message.contains("failed") || message.contains("ERROR") {
-            line.set_attribute("style", "color: #e74c3c;")?; // Red
-        } else if message.contains("success") || message.contains("verified") || message.contains("SUCCESS") {
-            line.set_attribute("style", "color: #2ecc71;")?; // Green
-        } else if message.contains("generating") || message.contains("wait") {
-            line.set_attribute("style", "color: #f39c12;")?; // Orange
-        }
-        
-        // Add line to log area
-        proof_log.append_child(&line)?;
-        
-        // Auto-scroll
-        let _ = js_sys::eval(&format!("document.getElementById('proof-log').scrollTop = document.getElementById('proof-log').scrollHeight"));
+// Re-enable the prove button once a proof attempt is over
+fn enable_prove_button(game: &GameInstance) {
+    game.view.prove_button.remove_attribute("disabled").ok();
+    game.view.prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: pointer; opacity: 1.0;").ok();
+}
+
+// Show the status of the proving service job (polled by sp1-bridge.js)
+fn show_proof_job_status(game: &GameInstance, status: &str, elapsed_ms: f64) {
+    let label = match status {
+        "queued" => "Waiting for a prover",
+        "executing" => "Executing the game transcript",
+        "proving" => "Generating the proof",
+        "verifying" => "Verifying the proof",
+        _ => return, // done/failed are shown by show_sp1_proof_result
+    };
+
+    if let Some(panel) = game.proof_panel.borrow().as_ref() {
+        panel.header.set_text_content(Some(&format!("⏳ {}... ({}s)", label, (elapsed_ms / 1000.0).round())));
     }
-    
+}
+
+// Show a simulated result: no proof was generated, so nothing can be verified
+fn show_simulated_proof_result(game: &GameInstance) -> Result<(), JsValue> {
+    // Re-enable the prove button
+    enable_prove_button(game);
+
+    log_to_proof_area(game, "SIMULATION ONLY: no proof was generated.")?;
+    log_to_proof_area(game, "=====================================")?;
+    log_to_proof_area(game, "This result is not verifiable. Start the proving service for a real SP1 proof.")?;
+
+    // Mark the proof panel as unverified
+    set_proof_header(game, "⚠️ Simulated Result (Not Verifiable)", "#f39c12")
+}
+
+// Add log message to the proof area of `game`
+fn log_to_proof_area(game: &GameInstance, message: &str) -> Result<(), JsValue> {
+    let Some(proof_log) = game.proof_panel.borrow().as_ref().map(|panel| panel.log.clone()) else {
+        return Ok(());
+    };
+    let document = proof_log.owner_document().expect("No owner document");
+
+    // Add timestamp
+    let date = js_sys::Date::new_0();
+    let timestamp = format!("[{:02}:{:02}:{:02}] ",
+        date.get_hours(),
+        date.get_minutes(),
+        date.get_seconds()
+    );
+
+    // Create new line
+    let line = document.create_element("div")?;
+    line.set_text_content(Some(&format!("{}{}", timestamp, message)));
+
+    // Set line color
+    if message.contains("error") || message.contains("failed") || message.contains("ERROR") {
+        line.set_attribute("style", "color: #e74c3c;")?; // Red
+    } else if message.contains("success") || message.contains("verified") || message.contains("SUCCESS") {
+        line.set_attribute("style", "color: #2ecc71;")?; // Green
+    } else if message.contains("generating") || message.contains("wait") {
+        line.set_attribute("style", "color: #f39c12;")?; // Orange
+    }
+
+    // Add line to log area
+    proof_log.append_child(&line)?;
+
+    // Auto-scroll
+    proof_log.set_scroll_top(proof_log.scroll_height());
+
     Ok(())
 }
 
 // Start SP1 proof generation
-fn start_sp1_proof() {
+fn start_sp1_proof(game: &Rc<GameInstance>) {
     let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
-    play_sound("button-click.mp3");
-    
+
     // Get game state
-    let (moves, timer, score, matched_pairs, is_game_over) = GAME_STATE.with(|state| {
-        let game_state = state.borrow();
+    let (difficulty, seed, client_seed, session, flips, moves, timer, remaining_time, score, matched_pairs, is_game_over) = {
+        let game_state = game.state.borrow();
         (
-            game_state.moves,
-            game_state.timer,
-            game_state.score,
-            game_state.matched_pairs.len(),
-            game_state.game_over
+            game_state.game.difficulty(),
+            game_state.seed,
+            game_state.client_seed,
+            game_state.session.as_ref().map(|session| (session.id.clone(), session.commitment, session.nonce)),
+            game_state.game.flips().to_vec(),
+            game_state.game.moves(),
+            game_state.game.elapsed(),
+            game_state.game.remaining_time(),
+            game_state.game.score(),
+            game_state.game.matched_pairs(),
+            game_state.game.is_over()
         )
-    });
-    
+    };
+
     // If game is not over, show error
     if !is_game_over {
         window.alert_with_message("Game is not completed yet! You need to finish the game first.").ok();
         return;
     }
-    
+
+    publish(game, GameEvent::ProofRequested {
+        score,
+        moves,
+        time: timer,
+        difficulty,
+        session_id: session.as_ref().map(|(session_id, _, _)| session_id.clone()),
+    });
+
     // Disable the prove button
-    if let Some(prove_button) = document.get_element_by_id("prove-game") {
-        prove_button.set_attribute("disabled", "true").ok();
-        prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #95a5a6; color: white; border: none; border-radius: 5px; cursor: not-allowed;").ok();
-    }
-    
+    game.view.prove_button.set_attribute("disabled", "true").ok();
+    game.view.prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #95a5a6; color: white; border: none; border-radius: 5px; cursor: not-allowed;").ok();
+
     // Clear game area and add proof panel
-    show_proof_in_game_area(&document).ok();
-    
+    show_proof_in_game_area(game).ok();
+
     // Show proof start message
-    log_to_proof_area("Starting SP1 ZK Proof process...").ok();
-    log_to_proof_area(&format!("Game Info: Score: {}, Moves: {}, Time: {}s, Matched Pairs: {}", 
+    log_to_proof_area(game, "Starting SP1 ZK Proof process...").ok();
+    log_to_proof_area(game, &format!("Game Info: Score: {}, Moves: {}, Time: {}s, Matched Pairs: {}",
         score, moves, timer, matched_pairs)).ok();
-    
+
     // SP1 proof generation (delegated to JavaScript)
     // Communicate with JavaScript (sp1-bridge.js)
     let js_game_data = js_sys::Object::new();
-    let _ = js_sys::Reflect::set(&js_game_data, &"score".into(), &(score as u32).into());
-    let _ = js_sys::Reflect::set(&js_game_data, &"moves".into(), &(moves as u32).into());
-    let _ = js_sys::Reflect::set(&js_game_data, &"time".into(), &(timer as u32).into());
-    let _ = js_sys::Reflect::set(&js_game_data, &"matchedPairs".into(), &(matched_pairs as u32).into());
-    
-    // Call generateProof function in JavaScript
+    let _ = js_sys::Reflect::set(&js_game_data, &"score".into(), &score.into());
+    let _ = js_sys::Reflect::set(&js_game_data, &"moves".into(), &moves.into());
+    let _ = js_sys::Reflect::set(&js_game_data, &"time".into(), &timer.into());
+    let _ = js_sys::Reflect::set(&js_game_data, &"remainingTime".into(), &remaining_time.into());
+    let _ = js_sys::Reflect::set(&js_game_data, &"matchedPairs".into(), &matched_pairs.into());
+
+    // Transcript for the zkVM: the deck seed and every card flip in order
+    let js_flips: js_sys::Array = flips.iter().map(|&flip| JsValue::from(flip)).collect();
+    let _ = js_sys::Reflect::set(&js_game_data, &"difficulty".into(), &difficulty.name().into());
+    let _ = js_sys::Reflect::set(&js_game_data, &"seed".into(), &deck::format_seed(seed).into());
+    let _ = js_sys::Reflect::set(&js_game_data, &"clientSeed".into(), &deck::format_seed(client_seed).into());
+    if let Some((session_id, session_commitment, session_nonce)) = session {
+        let _ = js_sys::Reflect::set(&js_game_data, &"sessionId".into(), &session_id.into());
+        let _ = js_sys::Reflect::set(&js_game_data, &"commitment".into(), &hex::encode(&session_commitment).into());
+        let _ = js_sys::Reflect::set(&js_game_data, &"nonce".into(), &hex::encode(&session_nonce).into());
+    }
+    let _ = js_sys::Reflect::set(&js_game_data, &"flips".into(), &js_flips);
+
+    // Call generateProof function in JavaScript: it queues a proof job and polls it, reporting
+    // to this game's proof panel through the reporter
     // Note: You need to include this JSBridge in index.html
-    let _ = js_sys::eval(&format!("
-        if (window.SP1Bridge && typeof window.SP1Bridge.generateProof === 'function') {{
-            window.SP1Bridge.generateProof({});
-        }} else {{
-            document.getElementById('proof-log').innerHTML += '<div style=\"color: #e74c3c;\">[Error] SP1Bridge not found! Check sp1-bridge.js file.</div>';
-        }}
-    ", js_sys::JSON::stringify(&js_game_data).unwrap()));
+    let Some(bridge) = sp1_bridge() else {
+        log_to_proof_area(game, "[ERROR] SP1Bridge not found! Check sp1-bridge.js file.").ok();
+        return;
+    };
+    let Some(reporter) = ProofReporter::new(game) else {
+        return;
+    };
+    let proof = JsFuture::from(bridge.generate_proof(&js_game_data, &reporter.object));
+
+    wasm_bindgen_futures::spawn_local(async move {
+        // The bridge falls back to a simulation on its own, so this only catches bridge bugs
+        let outcome = proof.await;
+        if let Err(error) = &outcome {
+            web_sys::console::error_2(&"Proof generation failed:".into(), error);
+            if let Some(game) = reporter.target.game() {
+                show_sp1_proof_result(&game, false, "").ok();
+            }
+        }
+
+        // The bridge is done with the reporter's callbacks
+        drop(reporter);
+    });
+}
+
+// The proof panel a proof reports to: a game, as long as it still shows the panel the proof
+// started on (going back or restarting replaces it)
+#[derive(Clone)]
+struct ProofTarget {
+    game: Weak<GameInstance>,
+    log: Element,
+}
+
+impl ProofTarget {
+    fn game(&self) -> Option<Rc<GameInstance>> {
+        let game = self.game.upgrade()?;
+        let shown = game.proof_panel.borrow().as_ref().is_some_and(|panel| panel.log == self.log);
+        shown.then_some(game)
+    }
+}
+
+// What sp1-bridge.js reports one proof through: { proofLog, log(message), jobStatus(status,
+// elapsedMs), result(success, hash), browserVerification(status, detail), simulated() }, and
+// verify(kind, proof, publicValues) with programVKey when this build can check proofs itself. The
+// callbacks live until the bridge's promise settles; every game's proof gets its own
+struct ProofReporter {
+    object: js_sys::Object,
+    target: ProofTarget,
+    _log: Closure<dyn FnMut(String)>,
+    _job_status: Closure<dyn FnMut(String, f64)>,
+    _result: Closure<dyn FnMut(bool, String)>,
+    _browser_verification: Closure<dyn FnMut(String, String)>,
+    _simulated: Listener,
+    #[cfg(feature = "verifier")]
+    _verify: Closure<dyn FnMut(String, String, String) -> Result<JsValue, JsValue>>,
+}
+
+impl ProofReporter {
+    // A reporter for the proof panel `game` shows now, if any
+    fn new(game: &Rc<GameInstance>) -> Option<ProofReporter> {
+        let log = game.proof_panel.borrow().as_ref()?.log.clone();
+        let target = ProofTarget { game: Rc::downgrade(game), log };
+
+        let reporting = target.clone();
+        let log = Closure::wrap(Box::new(move |message: String| {
+            if let Some(game) = reporting.game() {
+                log_to_proof_area(&game, &message).ok();
+            }
+        }) as Box<dyn FnMut(String)>);
+        let reporting = target.clone();
+        let job_status = Closure::wrap(Box::new(move |status: String, elapsed_ms: f64| {
+            if let Some(game) = reporting.game() {
+                show_proof_job_status(&game, &status, elapsed_ms);
+            }
+        }) as Box<dyn FnMut(String, f64)>);
+        let reporting = target.clone();
+        let result = Closure::wrap(Box::new(move |success: bool, hash: String| {
+            if let Some(game) = reporting.game() {
+                show_sp1_proof_result(&game, success, &hash).ok();
+            }
+        }) as Box<dyn FnMut(bool, String)>);
+        let reporting = target.clone();
+        let browser_verification = Closure::wrap(Box::new(move |status: String, detail: String| {
+            if let Some(game) = reporting.game() {
+                show_browser_verification(&game, &status, &detail).ok();
+            }
+        }) as Box<dyn FnMut(String, String)>);
+        let reporting = target.clone();
+        let simulated = Closure::wrap(Box::new(move || {
+            if let Some(game) = reporting.game() {
+                show_simulated_proof_result(&game).ok();
+            }
+        }) as Box<dyn FnMut()>);
+
+        let object = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&object, &"proofLog".into(), &target.log);
+        let _ = js_sys::Reflect::set(&object, &"log".into(), log.as_ref());
+        let _ = js_sys::Reflect::set(&object, &"jobStatus".into(), job_status.as_ref());
+        let _ = js_sys::Reflect::set(&object, &"result".into(), result.as_ref());
+        let _ = js_sys::Reflect::set(&object, &"browserVerification".into(), browser_verification.as_ref());
+        let _ = js_sys::Reflect::set(&object, &"simulated".into(), simulated.as_ref());
+
+        // Proofs are checked against the program vkey embedded in this build, never the one the
+        // proving service reports
+        #[cfg(feature = "verifier")]
+        let verify = Closure::wrap(Box::new(|kind: String, proof: String, public_values: String| {
+            verifier::verify_game_proof(&kind, &proof, &public_values).map(JsValue::from).map_err(JsValue::from)
+        }) as Box<dyn FnMut(String, String, String) -> Result<JsValue, JsValue>>);
+        #[cfg(feature = "verifier")]
+        {
+            let _ = js_sys::Reflect::set(&object, &"verify".into(), verify.as_ref());
+            let _ = js_sys::Reflect::set(&object, &"programVKey".into(), &verifier::program_vkey().into());
+        }
+
+        Some(ProofReporter {
+            object,
+            target,
+            _log: log,
+            _job_status: job_status,
+            _result: result,
+            _browser_verification: browser_verification,
+            _simulated: simulated,
+            #[cfg(feature = "verifier")]
+            _verify: verify,
+        })
+    }
 }
 
 // Show SP1 proof result
-#[wasm_bindgen]
-pub fn show_sp1_proof_result(success: bool, hash: &str) -> Result<(), JsValue> {
-    let window = web_sys::window().expect("No global window");
-    let document = window.document().expect("No global document");
-    
+fn show_sp1_proof_result(game: &GameInstance, success: bool, hash: &str) -> Result<(), JsValue> {
     // Re-enable the prove button
-    if let Some(prove_button) = document.get_element_by_id("prove-game") {
-        prove_button.remove_attribute("disabled").ok();
-        prove_button.set_attribute("style", "padding: 15px 30px; font-size: 20px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: pointer; opacity: 1.0;").ok();
-    }
-    
+    enable_prove_button(game);
+
     if success {
         // Success sound
-        play_sound("success.mp3");
-        
-        log_to_proof_area("Proof verification SUCCESS!").ok();
-        log_to_proof_area(&format!("Proof Hash: {}", hash)).ok();
-        log_to_proof_area("=====================================").ok();
-        log_to_proof_area("This proof verifies the validity of your game score.").ok();
-        
+        game.play_sound("success.mp3");
+
+        log_to_proof_area(game, "Proof verified by the proving service")?;
+        log_to_proof_area(game, &format!("Proof Digest: {}", hash))?;
+        log_to_proof_area(game, "=====================================")?;
+        log_to_proof_area(game, "This proof verifies the validity of your game score.")?;
+
         // Add success message to proof panel
-        if let Some(proof_header) = document.get_element_by_id("proof-header") {
-            proof_header.set_text_content(Some("✅ Proof Successfully Generated"));
-            proof_header.set_attribute("style", "
-                font-size: 24px;
-                font-weight: bold;
-                text-align: center;
-                margin-bottom: 20px;
-                padding-bottom: 10px;
-                border-bottom: 1px solid #2ecc71;
-                color: #2ecc71;
-            ").ok();
-        }
-        
+        set_proof_header(game, "✅ Proof Successfully Generated", "#2ecc71")
     } else {
         // Error sound
-        play_sound("lose.mp3");
-        
-        log_to_proof_area("ERROR: Proof verification failed!").ok();
-        log_to_proof_area("Please try again or contact administrator.").ok();
-        
+        game.play_sound("lose.mp3");
+
+        log_to_proof_area(game, "ERROR: Proof verification failed!")?;
+        log_to_proof_area(game, "Please try again or contact administrator.")?;
+
         // Add error message to proof panel
-        if let Some(proof_header) = document.get_element_by_id("proof-header") {
-            proof_header.set_text_content(Some("❌ Proof Generation Failed"));
-            proof_header.set_attribute("style", "
-                font-size: 24px;
-                font-weight: bold;
-                text-align: center;
-                margin-bottom: 20px;
-                padding-bottom: 10px;
-                border-bottom: 1px solid #e74c3c;
-                color: #e74c3c;
-            ").ok();
-        }
+        set_proof_header(game, "❌ Proof Generation Failed", "#e74c3c")
     }
-    
-    Ok(())
 }
 
-// Generate game proof (simulation)
-fn generate_game_proof() -> Vec<u8> {
-    // Note: This function doesn't create a real SP1 proof, it's just for demonstration
-    // In a real application, the SP1 library would be used
-    
-    // Safely copy game state
-    let (moves, timer, score, matched_pairs, is_game_over) = GAME_STATE.with(|state| {
-        let game_state = state.borrow();
-        (
-            game_state.moves,
-            game_state.timer,
-            game_state.score,
-            game_state.matched_pairs.clone(),
-            game_state.game_over
-        )
-    });
-    
-    // Create sample proof data
-    let mut proof = Vec::new();
-    
-    // Add game info to proof
-    proof.extend_from_slice(&moves.to_le_bytes());
-    proof.extend_from_slice(&timer.to_le_bytes());
-    proof.extend_from_slice(&score.to_le_bytes());
-    proof.push(if is_game_over { 1 } else { 0 });
-    
-    // Add matched cards
-    for &pair in &matched_pairs {
-        proof.push(pair as u8);
+// Show whether the browser verified the proof itself (status: verified, failed or unavailable)
+fn show_browser_verification(game: &GameInstance, status: &str, detail: &str) -> Result<(), JsValue> {
+    let (message, header, color) = match status {
+        "verified" => (format!("Verified in your browser: {}", detail), Some("✅ Proof Verified in Your Browser"), "#2ecc71"),
+        "failed" => (format!("Browser verification FAILED: {}", detail), Some("❌ Proof Rejected by Your Browser"), "#e74c3c"),
+        _ => (format!("Browser verification unavailable: {}", detail), None, "#f39c12"),
+    };
+
+    log_to_proof_area(game, &message)?;
+    if status == "failed" {
+        game.play_sound("lose.mp3");
+    }
+
+    // Only a check made here changes the verdict shown in the header
+    match header {
+        Some(header) => set_proof_header(game, header, color),
+        None => Ok(()),
     }
-    
-    proof
 }
 
-// Start the game - this function can be called from HTML
+// Start the default game - this function can be called from HTML
 #[wasm_bindgen]
 pub fn start_game_from_js() {
-    start_game();
-}
\ No newline at end of file
+    with_default_game(MemoryGame::start);
+}
@@ -0,0 +1,492 @@
+// replay.rs - watch a recorded game on the normal board
+//
+// The deck is rebuilt from the recorded seed and the recorded actions are applied to a fresh
+// engine at their recorded times. Seeking re-applies the actions from the start, so every
+// position shows exactly the state the player saw.
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element, HtmlSelectElement};
+use wasm_bindgen::JsCast;
+use std::rc::Rc;
+use memory_game_core::deck;
+use memory_game_core::difficulty::Difficulty;
+use memory_game_core::engine::{Command, Game, Phase, Rejection};
+use memory_game_core::replay_code::{CodeError, ReplayCode};
+use memory_game_core::transcript::{self, Action, Entry, Transcript};
+
+use crate::{clear_board, deal_cards, leave_game, listener, render_game_board, reset_game, set_onclick, update_card_visuals, update_game_stats, GameInstance, Interval, Listener, MemoryGame};
+
+// How often a playing replay advances, in milliseconds
+const FRAME_MS: u32 = 100;
+
+// Replay speeds offered by the speed selector
+const SPEEDS: [f64; 5] = [0.5, 1.0, 2.0, 4.0, 8.0];
+
+// A replay code has no timings, so its flips are shown one every CODE_FLIP_MS and pairs are
+// resolved after the same delay as in a live game
+const CODE_FLIP_MS: u64 = 700;
+const CODE_RESOLVE_MS: u64 = 1000;
+
+// A recorded game being replayed
+pub(crate) struct Replay {
+    difficulty: Difficulty,
+    seed: u64,
+    entries: Vec<Entry>, // From the game's Start, with times relative to it
+    position: usize, // Number of entries applied
+    clock_ms: f64, // Replay time since the Start
+    speed: f64,
+    playing: Option<Interval>, // Advances the replay while it plays
+    controls: ReplayControls,
+}
+
+// The replay bar shown in place of the game controls
+struct ReplayControls {
+    bar: Element,
+    play_button: Element,
+    speed_select: HtmlSelectElement,
+    position_label: Element,
+    _listeners: Vec<Listener>,
+}
+
+impl Replay {
+    // Replay `entries` (starting with the game's Start) on the deck dealt from `seed`. Fails if
+    // the engine refuses one of them, so a tampered transcript can't be shown as a real game.
+    pub(crate) fn check(difficulty: Difficulty, seed: u64, entries: &[Entry]) -> Result<(), String> {
+        transcript::rebuild(difficulty, seed, entries)
+            .map(|_| ())
+            .map_err(|(index, rejection)| format!("Entry {} ({}) is not a valid move: {:?}", index + 1, entries[index].action.name(), rejection))
+    }
+
+    // The game after the first `position` entries
+    fn game_at(&self, position: usize) -> Game {
+        transcript::rebuild(self.difficulty, self.seed, &self.entries[..position]).expect("replays are checked when they are loaded")
+    }
+
+    // Steps are the entries a moderator cares about; timer ticks are applied along the way
+    fn step_positions(&self) -> Vec<usize> {
+        self.entries.iter().enumerate()
+            .filter(|(_, entry)| !matches!(entry.action, Action::Tick { .. }))
+            .map(|(index, _)| index + 1)
+            .collect()
+    }
+
+    fn current_step(&self) -> usize {
+        self.step_positions().iter().filter(|&&position| position <= self.position).count()
+    }
+
+    fn seek(&mut self, position: usize) {
+        self.position = position.min(self.entries.len());
+        self.clock_ms = match self.position {
+            0 => 0.0,
+            position => self.entries[position - 1].at_ms as f64,
+        };
+    }
+}
+
+// Entries for a replay code's flips, with made-up timings and no timer ticks
+fn code_entries(code: &ReplayCode) -> Result<Vec<Entry>, String> {
+    let mut game = Game::new(code.difficulty, deck::shuffled_deck(code.seed, code.difficulty.pairs()));
+    let mut transcript = Transcript::new(0);
+    let mut at_ms = 0;
+
+    let apply = |game: &mut Game, transcript: &mut Transcript, command: Command, at_ms: u64| -> Result<(), Rejection> {
+        for event in &game.apply(command)? {
+            transcript.record(at_ms, Action::from_event(event));
+        }
+        Ok(())
+    };
+
+    apply(&mut game, &mut transcript, Command::Start, at_ms).ok(); // A fresh game always starts
+    for (number, &flip) in code.flips.iter().enumerate() {
+        at_ms += CODE_FLIP_MS;
+        apply(&mut game, &mut transcript, Command::Flip(flip as usize), at_ms)
+            .map_err(|rejection| format!("Flip {} (card {}) is not a valid move: {:?}", number + 1, flip, rejection))?;
+        if game.phase() == Phase::Checking {
+            at_ms += CODE_RESOLVE_MS;
+            apply(&mut game, &mut transcript, Command::Resolve, at_ms).map_err(|rejection| format!("Flip {} can't be resolved: {:?}", number + 1, rejection))?;
+        }
+    }
+
+    Ok(transcript.entries)
+}
+
+// Why a replay code was refused, for the player
+fn code_error(error: CodeError) -> String {
+    match error {
+        CodeError::OutOfRange(flip) => format!("Card {} is not on the board", flip),
+        CodeError::Malformed => "Invalid replay code".to_string(),
+        CodeError::Version(version) => format!("Replay code version {} is not supported", version),
+        CodeError::Checksum => "The replay code is damaged (was it copied completely?)".to_string(),
+    }
+}
+
+#[wasm_bindgen]
+impl MemoryGame {
+    // Replay the latest game of a binary transcript (MemoryGame.transcriptBytes) on this board,
+    // paused at the start. Not possible while a game is being played.
+    #[wasm_bindgen(js_name = replayTranscript)]
+    pub fn replay_transcript(&self, bytes: &[u8]) -> Result<(), JsValue> {
+        let transcript = Transcript::decode(bytes).ok_or("Invalid transcript")?;
+        let game = transcript.last_game().ok_or("The transcript has no game to replay")?;
+        start_replay(&self.game, game.difficulty, game.seed, game.entries.to_vec())
+    }
+
+    // Shareable code for the latest game on this board: its deck and flips, as base64url text
+    // short enough for a URL fragment. Undefined until a game has started.
+    #[wasm_bindgen(js_name = replayCode)]
+    pub fn replay_code(&self) -> Option<String> {
+        let game_state = self.game.state.borrow();
+        let game = game_state.transcript.last_game()?;
+        ReplayCode { difficulty: game.difficulty, seed: game.seed, flips: game.flips() }.encode().ok()
+    }
+
+    // Replay the game of a replay code (MemoryGame.replayCode), one flip every 0.7 seconds
+    #[wasm_bindgen(js_name = replayFromCode)]
+    pub fn replay_from_code(&self, code: &str) -> Result<(), JsValue> {
+        replay_code_game(&self.game, code)
+    }
+
+    // Deal the deck of a replay code, so the player can try to beat the shared game. The deck
+    // doesn't come from a commit-reveal session, so the game can't be proven.
+    #[wasm_bindgen(js_name = challengeFromCode)]
+    pub fn challenge_from_code(&self, code: &str) -> Result<(), JsValue> {
+        challenge_code_deck(&self.game, code)
+    }
+
+    // Play the replay from its current position
+    #[wasm_bindgen(js_name = replayPlay)]
+    pub fn replay_play(&self) {
+        play(&self.game);
+    }
+
+    // Pause the replay
+    #[wasm_bindgen(js_name = replayPause)]
+    pub fn replay_pause(&self) {
+        pause(&self.game);
+    }
+
+    // Go to the next step (a flip, pair resolution or the end of the game)
+    #[wasm_bindgen(js_name = replayStep)]
+    pub fn replay_step(&self) {
+        step(&self.game, true);
+    }
+
+    // Go back to the previous step
+    #[wasm_bindgen(js_name = replayStepBack)]
+    pub fn replay_step_back(&self) {
+        step(&self.game, false);
+    }
+
+    // Show the board after `step` steps (0 is before the game starts)
+    #[wasm_bindgen(js_name = replaySeek)]
+    pub fn replay_seek(&self, step: u32) {
+        seek_step(&self.game, step as usize);
+    }
+
+    // Number of steps in the replay (0 when not replaying)
+    #[wasm_bindgen(js_name = replayStepCount)]
+    pub fn replay_step_count(&self) -> u32 {
+        self.game.replay.borrow().as_ref().map_or(0, |replay| replay.step_positions().len() as u32)
+    }
+
+    // Playback speed, 1.0 being the speed the game was played at. Snapped to the selector's
+    // speeds: 0.5, 1, 2, 4 or 8
+    #[wasm_bindgen(js_name = setReplaySpeed)]
+    pub fn set_replay_speed(&self, speed: f64) {
+        if let Some(replay) = self.game.replay.borrow_mut().as_mut() {
+            // Snap to the nearest speed the selector offers (NaN keeps the current speed)
+            if speed.is_nan() {
+                return;
+            }
+            let speed = SPEEDS.into_iter().fold(SPEEDS[0], |nearest, offered| if (offered - speed).abs() < (nearest - speed).abs() { offered } else { nearest });
+            replay.speed = speed;
+            replay.controls.speed_select.set_value(&speed.to_string());
+        }
+    }
+
+    // Leave the replay and deal a new deck
+    #[wasm_bindgen(js_name = exitReplay)]
+    pub fn exit_replay(&self) {
+        reset_game(&self.game);
+    }
+}
+
+pub(crate) fn start_replay(game: &Rc<GameInstance>, difficulty: Difficulty, seed: u64, entries: Vec<Entry>) -> Result<(), JsValue> {
+    let window = web_sys::window().expect("No global window");
+    let document = window.document().expect("No global document");
+
+    if !is_replaying(game) && game.state.borrow().game.is_active() {
+        return Err("Can't replay during a game".into());
+    }
+    Replay::check(difficulty, seed, &entries)?;
+
+    // Times relative to the game's Start
+    let start_ms = entries.first().map_or(0, |entry| entry.at_ms);
+    let entries = entries.into_iter().map(|entry| Entry { at_ms: entry.at_ms - start_ms, action: entry.action }).collect();
+
+    // Leave any previous replay, then stop everything the live game had running
+    stop_replay(game);
+    game.timer.take();
+    game.pending_check.take();
+
+    let controls = create_replay_bar(&document, game)?;
+    game.view.footer.set_attribute("style", "display: none;")?;
+    game.view.root.append_child(&controls.bar)?;
+
+    // The board shows the recorded deck; the live game's session doesn't apply to it
+    {
+        let mut game_state = game.state.borrow_mut();
+        game_state.difficulty = difficulty;
+        game_state.seed = seed;
+        game_state.session = None;
+        game_state.game = Game::new(difficulty, deck::shuffled_deck(seed, difficulty.pairs()));
+    }
+    game.view.difficulty_select.set_value(difficulty.name());
+    render_game_board(game);
+
+    game.replay.replace(Some(Replay {
+        difficulty,
+        seed,
+        entries,
+        position: 0,
+        clock_ms: 0.0,
+        speed: 1.0,
+        playing: None,
+        controls,
+    }));
+    show_position(game);
+
+    Ok(())
+}
+
+// Deal the deck from `seed` in place of a session deck
+fn start_challenge(game: &Rc<GameInstance>, difficulty: Difficulty, seed: u64) -> Result<(), JsValue> {
+    if !is_replaying(game) && game.state.borrow().game.is_active() {
+        return Err("Can't start a challenge during a game".into());
+    }
+
+    leave_game(game);
+
+    // A client seed of 0 turns away the session still pending for the previous deck
+    {
+        let mut game_state = game.state.borrow_mut();
+        game_state.difficulty = difficulty;
+        game_state.client_seed = 0;
+        game_state.session = None;
+    }
+    game.view.difficulty_select.set_value(difficulty.name());
+
+    deal_cards(game, seed);
+    update_game_stats(game);
+
+    Ok(())
+}
+
+// Open the replay or challenge in a page's URL fragment (#replay=CODE or #challenge=CODE).
+// Other fragments are left alone.
+pub(crate) fn open_fragment(game: &Rc<GameInstance>, fragment: &str) -> Result<(), JsValue> {
+    let fragment = fragment.strip_prefix('#').unwrap_or(fragment);
+    if let Some(code) = fragment.strip_prefix("replay=") {
+        replay_code_game(game, code)
+    } else if let Some(code) = fragment.strip_prefix("challenge=") {
+        challenge_code_deck(game, code)
+    } else {
+        Ok(())
+    }
+}
+
+fn replay_code_game(game: &Rc<GameInstance>, code: &str) -> Result<(), JsValue> {
+    let code = ReplayCode::decode(code).map_err(code_error)?;
+    start_replay(game, code.difficulty, code.seed, code_entries(&code)?)
+}
+
+fn challenge_code_deck(game: &Rc<GameInstance>, code: &str) -> Result<(), JsValue> {
+    let code = ReplayCode::decode(code).map_err(code_error)?;
+    start_challenge(game, code.difficulty, code.seed)
+}
+
+// Remove the replay bar and bring the game controls back (the caller deals a new deck)
+pub(crate) fn stop_replay(game: &GameInstance) {
+    if let Some(replay) = game.replay.take() {
+        replay.controls.bar.remove();
+        game.view.footer.set_attribute("style", crate::FOOTER_STYLE).ok();
+        clear_board(game);
+    }
+}
+
+pub(crate) fn is_replaying(game: &GameInstance) -> bool {
+    game.replay.borrow().is_some()
+}
+
+// Show the board, stats and replay bar for the replay's current position
+fn show_position(game: &GameInstance) {
+    let Some((state, label, finished, playing)) = game.replay.borrow().as_ref().map(|replay| {
+        let steps = replay.step_positions().len();
+        let label = format!("Step {} / {}", replay.current_step(), steps);
+        (replay.game_at(replay.position), label, replay.position == replay.entries.len(), replay.playing.is_some())
+    }) else {
+        return;
+    };
+
+    game.state.borrow_mut().game = state;
+    update_card_visuals(game);
+    update_game_stats(game);
+
+    if let Some(replay) = game.replay.borrow().as_ref() {
+        let label = if finished { format!("{} (end)", label) } else { label };
+        replay.controls.position_label.set_text_content(Some(&label));
+        replay.controls.play_button.set_text_content(Some(if playing { "⏸ Pause" } else { "▶ Play" }));
+    }
+}
+
+fn play(game: &Rc<GameInstance>) {
+    let window = web_sys::window().expect("No global window");
+
+    {
+        let mut replay = game.replay.borrow_mut();
+        let Some(replay) = replay.as_mut() else { return };
+        if replay.playing.is_some() {
+            return;
+        }
+
+        // Play a finished replay again from the start
+        if replay.position == replay.entries.len() {
+            replay.seek(0);
+        }
+
+        let frame = listener(game, |game| {
+            let changed = {
+                let mut replay = game.replay.borrow_mut();
+                let Some(replay) = replay.as_mut() else { return };
+                replay.clock_ms += FRAME_MS as f64 * replay.speed;
+
+                let before = replay.position;
+                while replay.position < replay.entries.len() && replay.entries[replay.position].at_ms as f64 <= replay.clock_ms {
+                    replay.position += 1;
+                }
+                if replay.position == replay.entries.len() {
+                    replay.playing = None;
+                }
+                replay.position != before
+            };
+            if changed {
+                show_position(game);
+            }
+        });
+
+        let interval_id = window
+            .set_interval_with_callback_and_timeout_and_arguments_0(frame.as_ref().unchecked_ref(), FRAME_MS as i32)
+            .expect("Could not create interval");
+        replay.playing = Some(Interval { id: interval_id, _callback: frame });
+    }
+
+    show_position(game);
+}
+
+fn pause(game: &GameInstance) {
+    if let Some(replay) = game.replay.borrow_mut().as_mut() {
+        replay.playing = None;
+    }
+    show_position(game);
+}
+
+fn step(game: &GameInstance, forward: bool) {
+    if let Some(replay) = game.replay.borrow_mut().as_mut() {
+        replay.playing = None;
+        let steps = replay.step_positions();
+        let position = if forward {
+            steps.into_iter().find(|&position| position > replay.position).unwrap_or(replay.entries.len())
+        } else {
+            steps.into_iter().rev().find(|&position| position < replay.position).unwrap_or(0)
+        };
+        replay.seek(position);
+    }
+    show_position(game);
+}
+
+fn seek_step(game: &GameInstance, step: usize) {
+    if let Some(replay) = game.replay.borrow_mut().as_mut() {
+        replay.playing = None;
+        let position = match step {
+            0 => 0,
+            step => replay.step_positions().get(step - 1).copied().unwrap_or(replay.entries.len()),
+        };
+        replay.seek(position);
+    }
+    show_position(game);
+}
+
+fn create_replay_bar(document: &Document, game: &Rc<GameInstance>) -> Result<ReplayControls, JsValue> {
+    let bar = document.create_element("div")?;
+    bar.set_class_name("replay-bar");
+    bar.set_attribute("style", "height: 138px; display: flex; justify-content: center; align-items: center; gap: 15px;")?;
+
+    let button_style = "padding: 12px 20px; font-size: 18px; background-color: #3498db; color: white; border: none; border-radius: 5px; cursor: pointer;";
+
+    let back_button = document.create_element("button")?;
+    back_button.set_attribute("style", button_style)?;
+    back_button.set_text_content(Some("⏮ Step"));
+    let back_listener = listener(game, |game| step(game, false));
+    set_onclick(&back_button, &back_listener);
+
+    let play_button = document.create_element("button")?;
+    play_button.set_attribute("style", button_style)?;
+    play_button.set_text_content(Some("▶ Play"));
+    let play_listener = listener(game, |game| {
+        let playing = game.replay.borrow().as_ref().is_some_and(|replay| replay.playing.is_some());
+        if playing {
+            pause(game);
+        } else {
+            play(game);
+        }
+    });
+    set_onclick(&play_button, &play_listener);
+
+    let forward_button = document.create_element("button")?;
+    forward_button.set_attribute("style", button_style)?;
+    forward_button.set_text_content(Some("Step ⏭"));
+    let forward_listener = listener(game, |game| step(game, true));
+    set_onclick(&forward_button, &forward_listener);
+
+    // Speed selector
+    let speed_select = document.create_element("select")?.dyn_into::<HtmlSelectElement>()?;
+    speed_select.set_attribute("style", "padding: 12px; font-size: 18px; background-color: #440e43; color: white; border: none; border-radius: 5px; cursor: pointer;")?;
+    for speed in SPEEDS {
+        let option = document.create_element("option")?;
+        option.set_attribute("value", &speed.to_string())?;
+        option.set_text_content(Some(&format!("{}x", speed)));
+        if speed == 1.0 {
+            option.set_attribute("selected", "true")?;
+        }
+        speed_select.append_child(&option)?;
+    }
+    let speed_listener = listener(game, |game| {
+        if let Some(replay) = game.replay.borrow_mut().as_mut() {
+            replay.speed = replay.controls.speed_select.value().parse().unwrap_or(1.0);
+        }
+    });
+    speed_select.set_onchange(Some(speed_listener.as_ref().unchecked_ref()));
+
+    let position_label = document.create_element("div")?;
+    position_label.set_attribute("style", "font-size: 18px; color: white; min-width: 140px; text-align: center;")?;
+
+    let exit_button = document.create_element("button")?;
+    exit_button.set_attribute("style", "padding: 12px 20px; font-size: 18px; background-color: #e74c3c; color: white; border: none; border-radius: 5px; cursor: pointer;")?;
+    exit_button.set_text_content(Some("Exit Replay"));
+    let exit_listener = listener(game, reset_game);
+    set_onclick(&exit_button, &exit_listener);
+
+    bar.append_child(&back_button)?;
+    bar.append_child(&play_button)?;
+    bar.append_child(&forward_button)?;
+    bar.append_child(&speed_select)?;
+    bar.append_child(&position_label)?;
+    bar.append_child(&exit_button)?;
+
+    Ok(ReplayControls {
+        bar,
+        play_button,
+        speed_select,
+        position_label,
+        _listeners: vec![back_listener, play_listener, forward_listener, speed_listener, exit_listener],
+    })
+}
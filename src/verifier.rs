@@ -0,0 +1,67 @@
+// verifier.rs - check memory game proofs in the browser
+//
+// Players don't have to take the proving service's word for a score: Groth16 and PLONK proofs are
+// verified here with sp1-verifier. The program vkey hash is embedded when the module is built
+// (MEMORY_GAME_PROGRAM_VKEY, the output of `memory-proof vkey`), so the vkey the service reports
+// is never trusted. Core proofs can't be verified outside the proving service.
+use wasm_bindgen::prelude::*;
+use memory_game_core::hex;
+use memory_proof_lib::decode_public_values;
+use sp1_verifier::{Groth16Verifier, PlonkVerifier, GROTH16_VK_BYTES, PLONK_VK_BYTES};
+
+// The vkey hash of the memory game program this build trusts
+const PROGRAM_VKEY: Option<&str> = option_env!("MEMORY_GAME_PROGRAM_VKEY");
+
+// The game a verified proof commits to
+#[wasm_bindgen(getter_with_clone)]
+pub struct VerifiedGame {
+    pub score: u32,
+    pub moves: u32,
+    pub time: u32,
+    #[wasm_bindgen(js_name = matchedPairs)]
+    pub matched_pairs: u32,
+    #[wasm_bindgen(js_name = isComplete)]
+    pub is_complete: bool,
+    pub difficulty: String,
+    pub player: String,
+    pub issuer: String, // The backend that dealt the session; only trust games from one you know
+}
+
+// The program vkey hash proofs are verified against, or undefined if this build has none pinned
+#[wasm_bindgen]
+pub fn program_vkey() -> Option<String> {
+    PROGRAM_VKEY.map(String::from)
+}
+
+// Verify a `plonk` or `groth16` proof of the memory game program against the embedded vkey hash,
+// then decode its public values. `proof` and `public_values` are hex, as the proving service
+// returns them
+#[wasm_bindgen]
+pub fn verify_game_proof(kind: &str, proof: &str, public_values: &str) -> Result<VerifiedGame, JsError> {
+    let vkey_hash = PROGRAM_VKEY.ok_or_else(|| JsError::new("No program vkey was pinned when this game was built (MEMORY_GAME_PROGRAM_VKEY)"))?;
+    let proof = hex::decode(proof).ok_or_else(|| JsError::new("Proof is not hex"))?;
+    let public_values = hex::decode(public_values).ok_or_else(|| JsError::new("Public values are not hex"))?;
+
+    match kind {
+        "groth16" => Groth16Verifier::verify(&proof, &public_values, vkey_hash, *GROTH16_VK_BYTES)
+            .map_err(|error| JsError::new(&format!("Groth16 verification failed: {}", error)))?,
+        "plonk" => PlonkVerifier::verify(&proof, &public_values, vkey_hash, *PLONK_VK_BYTES)
+            .map_err(|error| JsError::new(&format!("PLONK verification failed: {}", error)))?,
+        _ => return Err(JsError::new(&format!("{} proofs can't be verified in the browser", kind))),
+    }
+
+    let values = decode_public_values(&public_values)
+        .map_err(|error| JsError::new(&format!("Failed to decode public values: {}", error)))?;
+    let difficulty = values.difficulty_preset().ok_or_else(|| JsError::new("Unknown difficulty"))?;
+
+    Ok(VerifiedGame {
+        score: values.score,
+        moves: values.moves,
+        time: values.time,
+        matched_pairs: values.matchedPairs,
+        is_complete: values.isComplete,
+        difficulty: difficulty.name().to_string(),
+        player: format!("0x{}", hex::encode(values.player.as_slice())),
+        issuer: format!("0x{}", hex::encode(values.issuer.as_slice())),
+    })
+}